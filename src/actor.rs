@@ -0,0 +1,179 @@
+// actors: state owned by one task, changed only by the messages in its mailbox, so no Mutex
+// (07e shares a counter through Arc<Mutex<_>> and answers with a oneshot; this is both, packaged)
+//
+//   tell  -> put a message in the mailbox, don't wait for the result
+//   ask   -> the message carries a Reply, await the answer
+//
+// a panic in `handle` restarts the actor with fresh state (up to `max_restarts` times),
+// the mailbox and the handles stay the same
+
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use tokio::sync::{Mutex, mpsc, oneshot};
+
+/// Something that reacts to messages of one type.
+///
+/// ```
+/// use rust_files::actor::{self, Actor, ActorConfig, Reply};
+///
+/// enum Msg { Add(u64), Get(Reply<u64>) }
+///
+/// struct Total(u64);
+///
+/// impl Actor for Total {
+///     type Msg = Msg;
+///     async fn handle(&mut self, msg: Msg) {
+///         match msg {
+///             Msg::Add(n) => self.0 += n,
+///             Msg::Get(reply) => reply.send(self.0),
+///         }
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let total = actor::spawn(ActorConfig::default(), || Total(0));
+/// total.tell(Msg::Add(40)).await.unwrap();
+/// total.tell(Msg::Add(2)).await.unwrap();
+/// assert_eq!(total.ask(Msg::Get).await, Ok(42));
+/// # }
+/// ```
+pub trait Actor: Send + 'static {
+    type Msg: Send + 'static;
+
+    /// Handles one message; the next one waits until this returns.
+    fn handle(&mut self, msg: Self::Msg) -> impl Future<Output = ()> + Send;
+}
+
+/// The answer slot of an `ask` message.
+pub struct Reply<T>(oneshot::Sender<T>);
+
+impl<T> Reply<T> {
+    /// Answers the `ask`. Nothing happens if the asker stopped waiting.
+    pub fn send(self, value: T) {
+        let _ = self.0.send(value);
+    }
+}
+
+impl<T> fmt::Debug for Reply<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Reply")
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActorError {
+    /// The actor is gone: it gave up after too many panics.
+    Stopped,
+    /// `try_tell` found the mailbox full.
+    MailboxFull,
+    /// The message was taken but never answered, e.g. `handle` panicked on it.
+    NoReply,
+}
+
+impl fmt::Display for ActorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ActorError::Stopped => write!(f, "actor stopped"),
+            ActorError::MailboxFull => write!(f, "mailbox full"),
+            ActorError::NoReply => write!(f, "actor did not reply"),
+        }
+    }
+}
+
+impl std::error::Error for ActorError {}
+
+#[derive(Clone, Debug)]
+pub struct ActorConfig {
+    /// Messages that can wait in the mailbox; `tell` waits (and `try_tell` fails) when it is full.
+    pub mailbox: usize,
+    /// Panics survived by restarting; the next one stops the actor for good.
+    pub max_restarts: u32,
+}
+
+impl Default for ActorConfig {
+    fn default() -> Self {
+        ActorConfig { mailbox: 32, max_restarts: 3 }
+    }
+}
+
+/// Address of a running actor. Cheap to clone; the actor stops once every handle is dropped
+/// and the mailbox is empty.
+pub struct Handle<M> {
+    tx: mpsc::Sender<M>,
+    restarts: Arc<AtomicU32>,
+}
+
+impl<M> Clone for Handle<M> {
+    fn clone(&self) -> Self {
+        Handle { tx: self.tx.clone(), restarts: self.restarts.clone() }
+    }
+}
+
+impl<M: Send + 'static> Handle<M> {
+    /// Fire and forget; waits only for room in the mailbox.
+    pub async fn tell(&self, msg: M) -> Result<(), ActorError> {
+        self.tx.send(msg).await.map_err(|_| ActorError::Stopped)
+    }
+
+    /// Like [`tell`](Handle::tell), but fails instead of waiting when the mailbox is full.
+    pub fn try_tell(&self, msg: M) -> Result<(), ActorError> {
+        self.tx.try_send(msg).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(_) => ActorError::MailboxFull,
+            mpsc::error::TrySendError::Closed(_) => ActorError::Stopped,
+        })
+    }
+
+    /// Sends the message built by `make` around a fresh [`Reply`] and waits for the answer,
+    /// e.g. `handle.ask(Msg::Get)` for a variant `Get(Reply<u64>)`.
+    pub async fn ask<R>(&self, make: impl FnOnce(Reply<R>) -> M) -> Result<R, ActorError> {
+        let (tx, rx) = oneshot::channel();
+        self.tell(make(Reply(tx))).await?;
+        rx.await.map_err(|_| ActorError::NoReply)
+    }
+
+    /// How often the actor was restarted after a panic.
+    pub fn restarts(&self) -> u32 {
+        self.restarts.load(Ordering::SeqCst)
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.tx.is_closed()
+    }
+}
+
+/// Starts the actor built by `make` on the tokio runtime. `make` is called again for every restart.
+pub fn spawn<A, F>(config: ActorConfig, make: F) -> Handle<A::Msg>
+where
+    A: Actor,
+    F: Fn() -> A + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(config.mailbox.max(1));
+    let restarts = Arc::new(AtomicU32::new(0));
+    let count = restarts.clone();
+    // the mailbox outlives a panicking run, the lock is released when its task unwinds
+    let mailbox = Arc::new(Mutex::new(rx));
+    tokio::spawn(async move {
+        loop {
+            let mut actor = make();
+            let mailbox = mailbox.clone();
+            let run = tokio::spawn(async move {
+                let mut rx = mailbox.lock().await;
+                while let Some(msg) = rx.recv().await {
+                    actor.handle(msg).await;
+                }
+            });
+            match run.await {
+                Err(e) if e.is_panic() && count.load(Ordering::SeqCst) < config.max_restarts => {
+                    count.fetch_add(1, Ordering::SeqCst);
+                }
+                // every handle dropped, or too many panics: dropping the mailbox fails pending asks
+                _ => break,
+            }
+        }
+    });
+    Handle { tx, restarts }
+}
@@ -1,57 +1,58 @@
 // problem: B. Heapify 1: https://codeforces.com/contest/2195/problem/B // Accepted
 // my first rust problem solution
 
-use std::io::{self, Read, BufWriter, Write};
+use std::io::{Read, Write};
+use rust_files::heap::one_based; // 1-indexed implicit tree helpers, see src/heap.rs
+use rust_files::output::Output; // shared buffered writer, see src/output.rs
+use rust_files::scanner::Scanner; // shared fast reader, see src/scanner.rs
+use rust_files::solve_cases; // test case loop, see src/cases.rs
 
 fn main() {
-    let mut buf = String::new();
-    io::stdin().read_to_string(&mut buf).unwrap();    
-    let mut tokens = buf.split_whitespace().map(|x| x.parse::<usize>().unwrap());
-    
-    let tc = tokens.next().unwrap() as usize;
-    
-    const SZ: usize = 200001; // 2e5+1
-    
-    let mut pos: Vec<usize> = Vec::with_capacity(SZ);
-    let mut num:usize;
-    
-    let mut out = BufWriter::new(io::stdout());
+    // was: read tc, then for _ in 0..tc { ... } with one pos buffer of size 2e5+1 reused by every case
+    solve_cases!(solve);
+}
 
-    for _ in 0..tc {
-        let n = tokens.next().unwrap() as usize;
-        pos.resize(SZ, 0);
-        let mut is_perm = true;
+fn solve<R: Read, W: Write>(sc: &mut Scanner<R>, out: &mut Output<W>) {
+    let n: usize = sc.next();
+    let mut pos: Vec<usize> = vec![0; n + 1];
 
-        for i in 1..=n {
-            num = tokens.next().unwrap();
-            pos[num as usize] = i;
-        }        
-        // println!("pos: {:?}", pos);
-        for i in 1..=n { // main loop
-            let mut p = pos[i];
-            if i>p {
-                while i>p{p*=2;}
-                if i != p {
-                    is_perm = false;
-                    break; // break1, even though nested ifs, breaks innermost enclosing loop. similar in c/c++/python
-                }
-            }else if i<p{
-                while i<p{p/=2;}
-                if i != p {
-                    is_perm = false;
-                    break; // break2
-                }
+    for i in 1..=n {
+        let num: usize = sc.next();
+        pos[num] = i;
+    }
+    // println!("pos: {:?}", pos);
+    // value i at position p: if p < i, doubling p must hit i (i on the left chain below p),
+    // if p > i, halving p must hit i (i is an ancestor of p)
+    let is_perm = (1..=n).all(|i| {
+        let p = pos[i];
+        if p <= i { one_based::on_left_chain(p, i) } else { one_based::is_ancestor(i, p) }
+    });
+/*
+    // first version, same check written out:
+    for i in 1..=n { // main loop
+        let mut p = pos[i];
+        if i>p {
+            while i>p{p*=2;}
+            if i != p {
+                is_perm = false;
+                break; // break1, even though nested ifs, breaks innermost enclosing loop. similar in c/c++/python
+            }
+        }else if i<p{
+            while i<p{p/=2;}
+            if i != p {
+                is_perm = false;
+                break; // break2
             }
         }
-        // println!("{}", if is_perm { "YES" } else { "NO" }); // ok
-        writeln!(out, "{}", if is_perm { "YES" } else { "NO" }).unwrap(); // faster
-        pos.clear();
     }
-    
+*/
+    // println!("{}", if is_perm { "YES" } else { "NO" }); // ok
+    out.yes_no(is_perm); // buffered, faster
 }
 
 // input output:
 /*
+testcase:
 2
 5
 1 4 3 2 5
@@ -63,6 +64,7 @@ NO
 */
 
 /*
+testcase:
 2
 3
 2 1 3
@@ -76,6 +78,8 @@ YES
 
 /*
 // if input given from terminal, press enter + ctrl+z, in terminal to signal eof
+// input is read with rust_files::scanner (src/lib.rs), so plain rustc on this file alone no longer works, use cargo run
+// to submit (or to use plain rustc): cargo run --bin bundle -- 04a_cp_input1  -> target/bundle/04a_cp_input1.rs, one self-contained file
 
 // from project root:
   pwsh7:  cat src/bin/input.txt | cargo run --bin 04a_cp_input1
@@ -1,58 +1,35 @@
-// input taking using macro_rules! - scanln and next (can use any)
+// input taking using macro_rules! - scanln and next, now read with the shared Scanner like 04a/04c/04d
 // idea from: https://codeforces.com/contest/2227/submission/373136651 (by silicalet)
 // problem: round 1096 - E. It All Went Sideways, https://codeforces.com/contest/2227/problem/E  
 
 #![allow(unused, non_snake_case, dead_code)]
-use std::io::{BufRead, Read, Write, stdout, stdin};
-
-
-/// Reads an entire line, splits it by whitespace, and parses elements into a Vec<T>
-/// Usage: let row = scanln!(i32); // or single element, let st = scanln!(String)[0];
-
-macro_rules! scanln {
-    ($t:ty) => {{ // when a macro has ($t:ty), it means you must pass the type inside the macro call
-        let ln: String = stdin().lock().lines()
-            .skip_while(|x| x.as_ref().unwrap().is_empty())
-            .next().unwrap().unwrap();
-        let v: Vec<$t> = ln.split_whitespace().map(|x| x.parse().unwrap()).collect();
-        v
-    }};
-}
-
-macro_rules! next {
-    () => {{
-        use std::io::Read;
-        let mut token = stdin().lock().bytes()
-            .skip_while(|x| x.as_ref().unwrap().is_ascii_whitespace())
-            .take_while(|x| !x.as_ref().unwrap().is_ascii_whitespace())
-            .map(|x| x.unwrap() as char)
-            .collect::<String>();
-        token.parse().unwrap()
-    }};
-}
-
-fn main() {    
-    let T: usize = next!(); // let T: usize = scanln!(usize)[0];
-    for _ in 1..=T {
-        solve();
-    }
+use std::io::{Read, Write};
+use rust_files::ops::{Min, Sum};
+use rust_files::outln;
+use rust_files::output::Output;
+use rust_files::prefix::PrefixAgg; // prefix/suffix folds, see src/prefix.rs
+use rust_files::scanner::Scanner; // was the scanln!/next! macros (still in src/scanner.rs)
+use rust_files::solve_cases; // test case loop, see src/cases.rs
+
+// was: let row = scanln!(i32); // or single element, let st = scanln!(String)[0];
+// was: let n: usize = next!();
+
+fn main() {
+    // was: let T: usize = next!(); then for _ in 1..=T { solve(&mut out); } on one Output::stdout()
+    solve_cases!(solve);
 }
 
-fn solve(){
-    let mut out = stdout().lock();
+fn solve<R: Read, W: Write>(sc: &mut Scanner<R>, out: &mut Output<W>) {
 
-    let n:usize = scanln!(usize)[0]; // or, let n:usize = next!();
-    let a: Vec<usize> = scanln!(usize); // or, let a: Vec<usize> = (0..n).map(|_| next!()).collect();
+    let n: usize = sc.next(); // was: scanln!(usize)[0]
+    let a: Vec<usize> = sc.vec(n); // was: scanln!(usize), the whole line
 
 
-    let mut suf = vec![0; n]; // suffix minimum array
-    suf[n - 1] = a[n - 1];
- 
-    for i in (0..n - 1).rev() {
-        suf[i] = suf[i + 1].min(a[i]);
-    }
+    let sum_agg = PrefixAgg::<usize, Sum>::new(&a);
+    let min_agg = PrefixAgg::<usize, Min>::new(&a);
+    let suf = &min_agg.suffixes()[..n]; // suffix minimum array, suf[i] = min(a[i..])
  
-    let sum: usize = a.iter().sum();
+    let sum: usize = sum_agg.prefix(n);
     let tmp: usize = suf.iter().sum();
     let b = sum - tmp;
  
@@ -66,13 +43,15 @@ fn solve(){
         }
         ans = ans.max(cnt[a[i]] - 1);
     } 
-    writeln!(out, "{}", b + ans);
+    outln!(out, "{}", b + ans);
 }
 
 
 
 /*
 // if input given from terminal, press enter + ctrl+z, in terminal to signal eof
+// input is read with rust_files::scanner (src/lib.rs), so plain rustc on this file alone no longer works, use cargo run
+// to submit (or to use plain rustc): cargo run --bin bundle -- 04b_cp_input2  -> target/bundle/04b_cp_input2.rs, one self-contained file
 
 // from project root:
   pwsh7:  cat src/bin/input.txt | cargo run --bin 04b_cp_input2
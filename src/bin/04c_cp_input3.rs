@@ -1,47 +1,26 @@
 // solution: https://codeforces.com/contest/2217/submission/370167287 (by trycatchcry)
 // problem: round 1091 - C. Grid Covering, https://codeforces.com/contest/2217/problem/C
-use std::io::{self, Read};
+use std::io::{Read, Write};
+use rust_files::number_theory::gcd; // was a local gcd(i64, i64), now shared
+use rust_files::output::Output;
+use rust_files::scanner::Scanner;
+use rust_files::solve_cases; // reads t and runs solve() t times, see src/cases.rs
 
-fn gcd(mut a: i64, mut b: i64) -> i64 {
-    while b != 0 {
-        let t = a % b;
-        a = b;
-        b = t;
-    }
-    a
-}
- 
-fn solve() {
-    let mut s = String::new();
-    io::stdin().read_to_string(&mut s).unwrap();
-    let mut it = s.split_whitespace();
- 
-    let t: usize = it.next().unwrap().parse().unwrap();
-    let mut out = String::new();
- 
-    for _ in 0..t {
-        let n: i64 = it.next().unwrap().parse().unwrap();
-        let m: i64 = it.next().unwrap().parse().unwrap();
-        let a: i64 = it.next().unwrap().parse().unwrap();
-        let b: i64 = it.next().unwrap().parse().unwrap();
- 
-        if gcd(n, a) == 1 && gcd(m, b) == 1 && gcd(n, m) <= 2 {
-            out.push_str("YES\n");
-        } else {
-            out.push_str("NO\n");
-        }
-    }
- 
-    print!("{}", out);
+fn solve<R: Read, W: Write>(sc: &mut Scanner<R>, out: &mut Output<W>) {
+    let (n, m, a, b): (i64, i64, i64, i64) = sc.next(); // tuples read one token per field
+
+    out.yes_no(gcd(n, a) == 1 && gcd(m, b) == 1 && gcd(n, m) <= 2);
 }
  
 fn main() {
-    solve();
+    solve_cases!(solve);
 }
 
 
 /*
 // if input given from terminal, press enter + ctrl+z, in terminal to signal eof
+// input is read with rust_files::scanner (src/lib.rs), so plain rustc on this file alone no longer works, use cargo run
+// to submit (or to use plain rustc): cargo run --bin bundle -- 04c_cp_input3  -> target/bundle/04c_cp_input3.rs, one self-contained file
 
 // from project root:
   pwsh7 :  rustc "src/bin/04c_cp_input3.rs" --crate-name run_program && .\run_program
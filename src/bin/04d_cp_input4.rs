@@ -1,53 +1,29 @@
 // idea from: solution: https://codeforces.com/contest/2217/submission/370167287 (by trycatchcry)
 // problem: round 1091 - C. Grid Covering, https://codeforces.com/contest/2217/problem/C
-use std::io::{self, Read};
-use std::str::FromStr;
+use std::io::{Read, Write};
+use rust_files::number_theory::gcd; // was a local gcd(i64, i64), now shared
+use rust_files::output::Output;
+use rust_files::scanner::Scanner; // replaces the old next<T: FromStr>(&mut SplitWhitespace) helper
+use rust_files::solve_cases; // the for _ in 0..t loop, see src/cases.rs
 
-fn next<T: FromStr>(it: &mut std::str::SplitWhitespace) -> T
-where T::Err: std::fmt::Debug, {
-    it.next().unwrap().parse().unwrap()
-}
-
-fn gcd(mut a: i64, mut b: i64) -> i64 {
-    while b != 0 {
-        let t = a % b;
-        a = b;
-        b = t;
-    }
-    a
-}
- 
-fn solve() {
-    let mut s = String::new();
-    io::stdin().read_to_string(&mut s).unwrap();
-    let mut it = s.split_whitespace(); 
-
-    let t: usize = next(&mut it); // it.next().unwrap().parse().unwrap();
-    let mut out = String::new();
- 
-    for _ in 0..t {
-        let n: i64 = next(&mut it);
-        let m: i64 = next(&mut it);
-        let a: i64 = next(&mut it);
-        let b: i64 = next(&mut it);
- 
-        if gcd(n, a) == 1 && gcd(m, b) == 1 && gcd(n, m) <= 2 {
-            out.push_str("YES\n");
-        } else {
-            out.push_str("NO\n");
-        }
-    }
+fn solve<R: Read, W: Write>(sc: &mut Scanner<R>, out: &mut Output<W>) {
+    let n: i64 = sc.next();
+    let m: i64 = sc.next();
+    let a: i64 = sc.next();
+    let b: i64 = sc.next();
  
-    print!("{}", out);
+    out.yes_no(gcd(n, a) == 1 && gcd(m, b) == 1 && gcd(n, m) <= 2);
 }
  
 fn main() {
-    solve();
+    solve_cases!(solve);
 }
 
 
 /*
 // if input given from terminal, press enter + ctrl+z, in terminal to signal eof
+// input is read with rust_files::scanner (src/lib.rs), so plain rustc on this file alone no longer works, use cargo run
+// to submit (or to use plain rustc): cargo run --bin bundle -- 04d_cp_input4  -> target/bundle/04d_cp_input4.rs, one self-contained file
 
 // from project root:
   pwsh7 :  rustc "src/bin/04d_cp_input4.rs" --crate-name run_program && .\run_program
@@ -9,7 +9,8 @@ tokio = { version = "1", features = ["full"] }
 
 use tokio::time::{sleep, Duration};
 
-async fn fetch_data(id: u32, sleep_time: u64) -> String {
+// pub(crate): tests/async_timelines.rs includes this file to time it on a virtual clock
+pub(crate) async fn fetch_data(id: u32, sleep_time: u64) -> String {
     // Simulate network delay
     sleep(Duration::from_millis(sleep_time)).await;
     format!("Data for id {}", id)
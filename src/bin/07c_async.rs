@@ -2,7 +2,7 @@
 
 use tokio::time::{sleep, Duration};
 
-async fn fetch_data(id: u32, sleep_sec: u64) -> String {
+pub(crate) async fn fetch_data(id: u32, sleep_sec: u64) -> String {
     // Simulate network delay
     sleep(Duration::from_secs(sleep_sec)).await;
     format!("Data for id {}", id)
@@ -31,6 +31,7 @@ why 43 waits for 42 to be finished, then "start its task" ?
 
 /*
 // Timeline: 
+// (checked on a virtual clock in tests/async_timelines.rs, which is why fetch_data is pub(crate))
 | Time | What happens                    |
 | ---- | ------------------------------- |
 | 0s   | data42 future created           |
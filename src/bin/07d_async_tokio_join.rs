@@ -2,18 +2,18 @@
 
 use tokio::time::{sleep, Duration};
 
-async fn fetch_data(id: u32, sleep_sec: u64) -> String {
+pub(crate) async fn fetch_data(id: u32, sleep_sec: u64) -> String {
     // wait time
     sleep(Duration::from_secs(sleep_sec)).await; // from_millis
     format!("Data for id {}", id)
 }
 
-async fn fetch_user(id: u32) -> String {
+pub(crate) async fn fetch_user(id: u32) -> String {
     sleep(Duration::from_secs(3)).await;
     format!("User {}", id)
 }
 
-async fn fetch_orders(user_id: u32) -> Vec<String> {
+pub(crate) async fn fetch_orders(user_id: u32) -> Vec<String> {
     sleep(Duration::from_secs(5)).await;
     vec![format!("Order for user {}", user_id)]
 }
@@ -34,6 +34,7 @@ async fn main(){
     println!("Received: {}", r43); // prints at 10 sec
 /*
 // Timeline:
+// (checked on a virtual clock in tests/async_timelines.rs, which is why the fetch_ fns are pub(crate))
 | Time | What happens       |
 | ---- | ------------------ |
 | 0s   | both futures start |
@@ -22,6 +22,7 @@ async fn main() {
 }
 
 // cargo run --bin 07b_function_async4_tokio_spawn
+// background_job on a timer (intervals, daily, pause/cancel): src/scheduler.rs, cargo run --bin 07k_scheduler
 
 /*
 // output:
@@ -36,6 +37,7 @@ Task A: iteration 3
 
 /*
 // MPSC (Multiple Producer, Single Consumer)
+// chained into stages with worker counts and dead letters: src/pipeline.rs, cargo run --bin 07h_pipeline
 use tokio::sync::mpsc;
 
 #[tokio::main]
@@ -88,6 +90,7 @@ async fn main() {
 
 /*
 // Advanced Control Flow - select! - Race Between Futures
+// as reusable helpers (with_timeout, CancelToken, race_all): src/cancel.rs, cargo run --bin 07j_cancel
 use tokio::time::{sleep, Duration};
 use tokio::sync::mpsc;
 
@@ -113,6 +116,7 @@ async fn main() {
 
 /*
 // Shared State with Async Mutex
+// same counter as an actor, no Mutex (the oneshot reply below is its `ask`): src/actor.rs, cargo run --bin 07i_actor_counter
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -141,6 +145,7 @@ async fn main() {
 
 /*
 // Async I/O Example: TCP Echo Server
+// runnable version with a small command protocol, limits and ctrl-c shutdown: src/line_server.rs, cargo run --bin line_server
 use tokio::net::{TcpListener, TcpStream};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
@@ -3,28 +3,55 @@
 // rand crate provides random number generation
 
 use rand::Rng; //use rand::rngs::OsRng; -> more secure for passwords/tokens etc
+use rust_files::http::{HttpClient, HttpError};
+use rust_files::users::{self, mock, UserClient};
 
-async fn fetch_data(url: &str) -> Result<String, reqwest::Error> {
+async fn fetch_data(url: &str) -> Result<String, HttpError> {
     // ^ url: &str vs String
-    let body: String = reqwest::get(url).await?.text().await?; // add reqwest in cargo.toml
+    // was: reqwest::get(url).await?.text().await? -> a new client per call, no timeout, a 404 page counted as success
+    // the shared client (src/http.rs) times out, retries 5xx/connect errors and turns non-2xx into HttpError::Status
+    let body: String = HttpClient::shared().get_text(url).await?;
     Ok(body)
 }
 
 #[tokio::main]
 async fn main() {
-    let base_url = String::from("https://jsonplaceholder.typicode.com/users");
-    
+    // args: [base url | --offline] [user id]
+    //   --offline serves the 10 users from src/users/users.json on a local port (same as the mock_api binary)
+    let mut args = std::env::args().skip(1);
+    let base_url = match args.next().as_deref() {
+        None => String::from(users::PUBLIC_API),
+        Some("--offline") => mock::spawn_local().await.expect("cannot start the local mock api"),
+        Some(url) => url.to_string(),
+    };
+
     let mut rng = rand::thread_rng();
-    // random id between 1 and 10 (inclusive)
-    let user_id = rng.gen_range(1..=10);
+    // random id between 1 and 10 (inclusive), unless one is given
+    let user_id: u32 = match args.next() {
+        Some(id) => id.parse().expect("user id must be a number"),
+        None => rng.gen_range(1..=10),
+    };
     println!("user id: {}", user_id);
-    let url = format!("{}/{}", base_url, user_id);
+    let url = format!("{}/users/{}", base_url, user_id);
     match fetch_data(&url).await {
         Ok(data) => println!("Fetched: \n{}", data),
         Err(e) => eprintln!("Error: {}", e),
     }
+
+    // same request, but deserialized into rust_files::users::User (serde) instead of printed as text
+    let client = UserClient::new(&base_url);
+    match client.get_user(user_id).await {
+        Ok(user) => {
+            println!("name: {} (@{})", user.name, user.username);
+            println!("email: {}", user.email);
+            println!("city: {} {}", user.address.city, user.address.zipcode);
+            println!("company: {}", user.company.name);
+        }
+        Err(e) => eprintln!("Error: {}", e),
+    }
 }
-// cargo run --bin test
+// cargo run --bin 07f_tokio_reqwest_rand_result                  (needs internet)
+// cargo run --bin 07f_tokio_reqwest_rand_result -- --offline 3    (no internet needed)
 
 /*
 add to Cargo.toml,
@@ -0,0 +1,62 @@
+// fetch_many - N async jobs, at most `concurrency` at a time, results in input order
+// 07c awaits the futures one after the other, so the delays add up; here they overlap
+
+use rust_files::fanout::{fetch_many, fetch_many_with_deadline, FetchError};
+use rust_files::users::{mock, UserClient};
+use tokio::time::{sleep, Duration, Instant};
+
+// same as 07c, but the delay is in ms and id 0 fails, to show a per-item error
+async fn fetch_data(id: u32, sleep_ms: u64) -> Result<String, String> {
+    sleep(Duration::from_millis(sleep_ms)).await;
+    if id == 0 {
+        return Err("no data for id 0".to_string());
+    }
+    Ok(format!("Data for id {}", id))
+}
+
+#[tokio::main]
+async fn main() {
+    let jobs: Vec<(u32, u64)> = vec![(42, 400), (43, 200), (0, 100), (44, 300), (45, 200), (46, 100)];
+
+    // 07c style: each .await starts the next job only after the previous one is done
+    let start = Instant::now();
+    for &(id, ms) in &jobs {
+        let r = fetch_data(id, ms).await;
+        println!("{:>5}ms  sequential {:?}", start.elapsed().as_millis(), r);
+    }
+    let sequential = start.elapsed();
+
+    // 3 at a time: 42, 43, 0 start at once, 44 takes the slot of 0 at 100ms, 45 the one of 43 at 200ms,
+    // 46 starts at 400ms -> done after ~500ms instead of 1300ms
+    let start = Instant::now();
+    let results = fetch_many(jobs.clone(), 3, |(id, ms)| fetch_data(id, ms)).await;
+    let bounded = start.elapsed();
+    for ((id, _), r) in jobs.iter().zip(&results) {
+        match r {
+            Ok(data) => println!("id {:>2}: {}", id, data),
+            Err(e) => println!("id {:>2}: error: {}", id, e),
+        }
+    }
+    println!("sequential: {:?}, fetch_many(concurrency 3): {:?}", sequential, bounded);
+
+    // with a deadline the batch returns after 250ms, unfinished jobs come back as DeadlineExceeded
+    let results = fetch_many_with_deadline(jobs.clone(), 3, Duration::from_millis(250), |(id, ms)| fetch_data(id, ms)).await;
+    let late = results.iter().filter(|r| matches!(r, Err(FetchError::DeadlineExceeded))).count();
+    println!("deadline 250ms: {} finished, {} cancelled", results.len() - late, late);
+
+    // the same over http, against the local mock api (src/users), id 11 does not exist
+    let client = UserClient::new(&mock::spawn_local().await.expect("cannot start the mock api"));
+    let users = fetch_many(1..=11, 4, move |id| {
+        let client = client.clone();
+        async move { client.get_user(id).await }
+    })
+    .await;
+    for (id, u) in (1..).zip(&users) {
+        match u {
+            Ok(u) => println!("user {:>2}: {} ({})", id, u.name, u.address.city),
+            Err(e) => println!("user {:>2}: {}", id, e),
+        }
+    }
+}
+
+// cargo run --bin 07g_fetch_many
@@ -0,0 +1,104 @@
+// pipeline: the mpsc producer/consumer from 07e, with several stages and bounded channels between them
+// lines of rust_notes.txt -> parse (4 workers) -> transform (2 workers) -> write (1 worker)
+// blank lines fail to parse and end up as dead letters instead of stopping the run
+
+use std::fmt;
+
+use rust_files::pipeline::Pipeline;
+use tokio::io::AsyncWriteExt;
+
+static READ_FILE_PATH: &str = "src/bin/rust_notes.txt";
+static WRITE_FILE_PATH: &str = "target/rust_notes_pipeline.txt";
+
+#[derive(Debug)]
+enum Kind {
+    Command, // git ...
+    Marker,  // /* */ ---
+    Text,
+}
+
+#[derive(Debug)]
+struct Note {
+    line_no: usize,
+    kind: Kind,
+    text: String,
+}
+
+struct EmptyLine;
+
+impl fmt::Display for EmptyLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "empty line")
+    }
+}
+
+async fn parse((line_no, line): (usize, String)) -> Result<Note, EmptyLine> {
+    let text = line.trim().to_string();
+    let kind = match text.as_str() {
+        "" => return Err(EmptyLine),
+        "/*" | "*/" | "---" => Kind::Marker,
+        t if t.starts_with("git ") => Kind::Command,
+        _ => Kind::Text,
+    };
+    Ok(Note { line_no, kind, text })
+}
+
+async fn transform(note: Note) -> Result<String, String> {
+    let words = note.text.split_whitespace().count();
+    Ok(format!("{:>3} {:<8} {:>2} words | {}", note.line_no, format!("{:?}", note.kind), words, note.text))
+}
+
+#[tokio::main]
+async fn main() {
+    let text = match tokio::fs::read_to_string(READ_FILE_PATH).await {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("Error: cannot read {}: {}", READ_FILE_PATH, e);
+            return;
+        }
+    };
+    let lines: Vec<(usize, String)> = text.lines().map(str::to_string).enumerate().map(|(i, l)| (i + 1, l)).collect();
+    let out = match tokio::fs::File::create(WRITE_FILE_PATH).await {
+        Ok(f) => std::sync::Arc::new(tokio::sync::Mutex::new(f)),
+        Err(e) => {
+            eprintln!("Error: cannot create {}: {}", WRITE_FILE_PATH, e);
+            return;
+        }
+    };
+
+    let mut pipeline = Pipeline::from_iter(8, lines)
+        .stage("parse", 4, 8, parse)
+        .stage("transform", 2, 8, transform);
+    // watch failures as they happen (otherwise they are in report.dead_letters at the end)
+    let mut dead = pipeline.dead_letters();
+    let watcher = tokio::spawn(async move {
+        while let Some(d) = dead.recv().await {
+            eprintln!("dead letter: {}", d);
+        }
+    });
+
+    let file = out.clone(); // to flush once the sink is done
+    let report = pipeline
+        .sink("write", 1, move |row: String| {
+            let out = out.clone();
+            async move {
+                println!("{}", row);
+                out.lock().await.write_all(format!("{}\n", row).as_bytes()).await
+            }
+        })
+        .await;
+    let _ = watcher.await;
+    // tokio's File hands writes to a background thread, the last one may still be in flight
+    if let Err(e) = file.lock().await.flush().await {
+        eprintln!("Error: cannot write {}: {}", WRITE_FILE_PATH, e);
+        return;
+    }
+
+    for s in &report.stages {
+        println!("{:<10} ok: {:>3}  failed: {:>3}", s.name, s.ok, s.failed);
+    }
+    println!("written to {}", WRITE_FILE_PATH);
+}
+
+// cargo run --bin 07h_pipeline
+// rows come out in the order they finish, not file order: 4 parse workers race each other
@@ -0,0 +1,57 @@
+// the shared counter from 07e (Arc<Mutex<i32>>, 10 tasks x 100 increments) as an actor:
+// the count lives inside one task and is only touched by its messages, so there is nothing to lock
+
+use rust_files::actor::{self, Actor, ActorConfig, Reply};
+
+#[derive(Debug)]
+enum CounterMsg {
+    Increment,
+    Get(Reply<u64>),
+    Crash, // to show the supervisor restarting the actor
+}
+
+struct Counter {
+    count: u64,
+}
+
+impl Actor for Counter {
+    type Msg = CounterMsg;
+
+    async fn handle(&mut self, msg: CounterMsg) {
+        match msg {
+            CounterMsg::Increment => self.count += 1,
+            CounterMsg::Get(reply) => reply.send(self.count),
+            CounterMsg::Crash => panic!("counter crashed at {}", self.count),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let counter = actor::spawn(ActorConfig { mailbox: 16, max_restarts: 1 }, || Counter { count: 0 });
+
+    let mut handles = vec![];
+    for _ in 0..10 {
+        let counter = counter.clone(); // a handle, not the state
+        handles.push(tokio::spawn(async move {
+            for _ in 0..100 {
+                counter.tell(CounterMsg::Increment).await.unwrap(); // waits when the mailbox (16) is full
+            }
+        }));
+    }
+    for h in handles {
+        h.await.unwrap();
+    }
+    println!("Final: {}", counter.ask(CounterMsg::Get).await.unwrap()); // 1000
+
+    // a panic inside the actor: it is restarted with a fresh Counter, the handle keeps working
+    counter.tell(CounterMsg::Crash).await.unwrap();
+    println!("after crash: {:?}, restarts: {}", counter.ask(CounterMsg::Get).await, counter.restarts()); // Ok(0), 1
+
+    // max_restarts is 1, so the second panic stops it for good
+    counter.tell(CounterMsg::Crash).await.unwrap();
+    println!("after second crash: {:?}", counter.ask(CounterMsg::Get).await); // Err(Stopped) or Err(NoReply)
+}
+
+// cargo run --bin 07i_actor_counter
+// (the "counter crashed" panic messages on stderr are expected)
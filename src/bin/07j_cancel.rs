@@ -0,0 +1,60 @@
+// timeouts and cancellation with rust_files::cancel (src/cancel.rs), built on the select! snippet in 07e
+
+use rust_files::cancel::{race_all, with_timeout, CancelToken};
+use tokio::sync::mpsc;
+use tokio::time::{sleep, Duration};
+
+// 07e's background_job, but it stops when told to instead of after 3 rounds
+async fn background_job(name: &'static str, token: CancelToken) {
+    let mut i = 0;
+    while !token.is_cancelled() {
+        i += 1;
+        println!("{}: iteration {}", name, i);
+        // wait for the next round or the cancel, whichever comes first
+        if token.run_until_cancelled(sleep(Duration::from_millis(100))).await.is_none() {
+            break;
+        }
+    }
+    println!("{}: cancelled after {} iterations", name, i);
+}
+
+async fn fetch_from(mirror: &'static str, ms: u64, ok: bool) -> Result<String, String> {
+    sleep(Duration::from_millis(ms)).await;
+    if ok { Ok(format!("data from {}", mirror)) } else { Err(format!("{} is down", mirror)) }
+}
+
+#[tokio::main]
+async fn main() {
+    // 1. the select! timeout from 07e, as a Result
+    let (tx, mut rx) = mpsc::channel(1);
+    tokio::spawn(async move {
+        sleep(Duration::from_millis(200)).await;
+        let _ = tx.send("Data arrived").await;
+    });
+    match with_timeout(rx.recv(), Duration::from_millis(100)).await {
+        Ok(msg) => println!("Received: {:?}", msg),
+        Err(e) => println!("{}", e), // timed out after 100ms
+    }
+
+    // 2. one token for the whole app, a child per group of tasks
+    let shutdown = CancelToken::new();
+    let group_b = shutdown.child();
+    let a = tokio::spawn(background_job("Task A", shutdown.child()));
+    let b = tokio::spawn(background_job("Task B", group_b.clone()));
+    sleep(Duration::from_millis(250)).await;
+    group_b.cancel(); // only B stops
+    sleep(Duration::from_millis(200)).await;
+    shutdown.cancel(); // now A too
+    let _ = tokio::join!(a, b);
+
+    // 3. ask three mirrors, keep the first good answer, drop the slower requests
+    let first = race_all(vec![
+        fetch_from("mirror-1", 300, true),
+        fetch_from("mirror-2", 50, false),
+        fetch_from("mirror-3", 120, true),
+    ])
+    .await;
+    println!("race_all: {:?}", first); // Ok("data from mirror-3")
+}
+
+// cargo run --bin 07j_cancel
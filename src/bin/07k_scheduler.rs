@@ -0,0 +1,46 @@
+// scheduler: 07e's background_job (3 rounds, 100ms apart) as named jobs on a timer, see src/scheduler.rs
+
+use chrono::{Local, NaiveTime, Timelike};
+use rust_files::scheduler::{MissedTick, Schedule, Scheduler};
+use tokio::time::{sleep, Duration, Instant};
+
+async fn background_job(name: &'static str, start: Instant, work: Duration) {
+    println!("{:>5}ms {}: start", start.elapsed().as_millis(), name);
+    sleep(work).await;
+}
+
+#[tokio::main]
+async fn main() {
+    let start = Instant::now();
+    let sched = Scheduler::new();
+
+    let every_100ms = Schedule::every(Duration::from_millis(100));
+    sched.add("Task A", every_100ms, move || background_job("Task A", start, Duration::from_millis(10))).unwrap();
+    // takes 150ms on a 100ms grid: with Delay the runs are spaced 150ms, with Skip they'd be 200ms, Burst catches up
+    let b = every_100ms.on_missed(MissedTick::Delay);
+    sched.add("Task B", b, move || background_job("Task B", start, Duration::from_millis(150))).unwrap();
+    let once = Schedule::once(Duration::from_millis(250));
+    sched.add("report", once, move || background_job("report", start, Duration::ZERO)).unwrap();
+    // next full minute, then daily at that time
+    let now = Local::now().time();
+    let at = NaiveTime::from_hms_opt((now.hour() + (now.minute() + 1) / 60) % 24, (now.minute() + 1) % 60, 0).unwrap();
+    sched.add("backup", Schedule::daily_at(at), move || background_job("backup", start, Duration::ZERO)).unwrap();
+
+    sleep(Duration::from_millis(320)).await;
+    sched.pause("Task A");
+    println!("-- paused Task A");
+    for job in sched.list() {
+        let (name, when) = (&job.name, job.schedule.when);
+        println!("   {:<7} runs: {:<2} paused: {:<5} running: {:<5} {:?}", name, job.runs, job.paused, job.running, when);
+    }
+    sleep(Duration::from_millis(200)).await;
+    sched.resume("Task A");
+    sched.cancel("Task B");
+    println!("-- resumed Task A, cancelled Task B");
+    sleep(Duration::from_millis(200)).await;
+
+    sched.shutdown().await; // waits for runs in progress
+    println!("{:>5}ms shut down", start.elapsed().as_millis());
+}
+
+// cargo run --bin 07k_scheduler
@@ -0,0 +1,35 @@
+// timing: test3's quadratic length_of_longest_substring vs the O(n) window::longest_unique_substring
+// usage (from project root, release matters for timings):
+//   cargo run --release --bin bench_longest_substring
+
+use std::time::{Duration, Instant};
+
+use rust_files::leetcode::p0003_longest_substring::Solution;
+use rust_files::window::longest_unique_substring;
+
+fn time<R>(f: impl FnOnce() -> R) -> (R, Duration) {
+    let start = Instant::now();
+    let r = f();
+    (r, start.elapsed())
+}
+
+fn main() {
+    let mut rng = fastrand::Rng::with_seed(3);
+
+    println!("{:>8} {:>8} {:>14} {:>14}", "n", "answer", "old (test3)", "window");
+    for n in [1_000, 10_000, 100_000] {
+        let s: String = (0..n).map(|_| rng.lowercase()).collect();
+        let (fast, t_fast) = time(|| longest_unique_substring(&s));
+        let (slow, t_slow) = time(|| Solution::length_of_longest_substring(s.clone()));
+        assert_eq!(slow as usize, fast, "solutions disagree on n = {}", n);
+        println!("{:>8} {:>8} {:>14} {:>14}", n, fast, format!("{:.2?}", t_slow), format!("{:.2?}", t_fast));
+    }
+}
+
+/*
+// release build, example run (random lowercase strings):
+       n   answer    old (test3)         window
+    1000       17       297.63µs        23.57µs
+   10000       18        17.55ms       233.55µs
+  100000       20          1.25s        33.34ms
+*/
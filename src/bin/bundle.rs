@@ -0,0 +1,65 @@
+// turns a cp solution that uses rust_files into one self-contained file for submission
+// usage (from project root):
+//   cargo run --bin bundle -- src/bin/04a_cp_input1.rs            -> target/bundle/04a_cp_input1.rs
+//   cargo run --bin bundle -- 04b_cp_input2 -o submit.rs          (bin names work too)
+//   cargo run --bin bundle -- 04c_cp_input3 --no-check            (skip the rustc test build)
+// the result is compiled with `rustc --edition 2021` (what codeforces uses) to make sure it stands alone
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use rust_files::bundle;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let (mut input, mut output, mut check) = (None, None, true);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" => output = args.next().map(PathBuf::from),
+            "--no-check" => check = false,
+            _ => input = Some(arg),
+        }
+    }
+    let Some(input) = input else {
+        eprintln!("usage: bundle <src/bin/file.rs | bin name> [-o out.rs] [--no-check]");
+        std::process::exit(2);
+    };
+    if let Err(e) = run(&input, output, check) {
+        eprintln!("{}: {}", input, e);
+        std::process::exit(1);
+    }
+}
+
+fn run(input: &str, output: Option<PathBuf>, check: bool) -> Result<(), String> {
+    let name = Path::new(input).file_stem().unwrap_or_default().to_string_lossy().into_owned();
+    let path = if input.ends_with(".rs") { PathBuf::from(input) } else { Path::new("src/bin").join(format!("{}.rs", name)) };
+    let source = std::fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+
+    let lib = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs");
+    let b = bundle::bundle(&source, &lib).map_err(|e| e.to_string())?;
+
+    let out = output.unwrap_or_else(|| Path::new("target/bundle").join(format!("{}.rs", name)));
+    if let Some(dir) = out.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&out, &b.code).map_err(|e| format!("cannot write {}: {}", out.display(), e))?;
+    let modules = if b.modules.is_empty() { "none".to_string() } else { b.modules.join(", ") };
+    println!("wrote {} ({} bytes), inlined: {}", out.display(), b.code.len(), modules);
+
+    if check {
+        let exe = std::env::temp_dir().join(format!("bundle_check_{}", name));
+        let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+        let result = Command::new(rustc)
+            .args(["--edition", "2021", "-O", "--crate-name", "bundle_check", "-o"])
+            .arg(&exe)
+            .arg(&out)
+            .output()
+            .map_err(|e| format!("cannot run rustc: {}", e))?;
+        let _ = std::fs::remove_file(&exe);
+        if !result.status.success() {
+            return Err(format!("bundle does not compile:\n{}", String::from_utf8_lossy(&result.stderr)));
+        }
+        println!("rustc --edition 2021: ok");
+    }
+    Ok(())
+}
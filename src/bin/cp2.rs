@@ -1,26 +1,6 @@
-// 
-
-#![allow(unused)]
-
-struct Solution;
-
-impl Solution {
-    pub fn reverse(x: i32) -> i32 {
-        let mut num = x as i64;
-        let mut result: i64 = 0;
-        
-        while num != 0 {
-            result = result * 10 + num % 10;
-            num /= 10;
-        }
-        
-        if result > i32::MAX as i64 || result < i32::MIN as i64 {
-            0
-        } else {
-            result as i32
-        }
-    }
-}
+// leetcode: 7. Reverse Integer
+// the solution moved to src/leetcode/p0007_reverse_integer.rs, samples are checked by: cargo run --bin leetcode -- 7
+use rust_files::leetcode::p0007_reverse_integer::Solution;
 
 fn main() {
     let v = vec![123, -123, 120, 0, 1534236469]; // Vec<i32>
@@ -37,4 +17,4 @@ fn main() {
 1534236469: 0
 */
 
-// rustc "src/bin/cp2.rs" --crate-name run_program && .\run_program
+// cargo run --bin cp2
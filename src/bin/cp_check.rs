@@ -0,0 +1,104 @@
+// runs a cp solution against the `/* testcase: ... */` samples at the bottom of its file
+// usage (from project root):
+//   cargo run --bin cp_check -- src/bin/04b_cp_input2.rs
+//   cargo run --bin cp_check -- 04a_cp_input1 04c_cp_input3     (bin names work too)
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use rust_files::testcase::{self, Mismatch};
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() {
+        eprintln!("usage: cp_check <src/bin/file.rs | bin name>...");
+        std::process::exit(2);
+    }
+    let mut all_passed = true;
+    for arg in &args {
+        match check(arg) {
+            Ok(passed) => all_passed &= passed,
+            Err(e) => {
+                eprintln!("{}: {}", arg, e);
+                all_passed = false;
+            }
+        }
+    }
+    if !all_passed {
+        std::process::exit(1);
+    }
+}
+
+// accepts "src/bin/x.rs", "x.rs" or "x", returns (bin name, source path)
+fn resolve(arg: &str) -> (String, PathBuf) {
+    let name = Path::new(arg).file_stem().unwrap_or_default().to_string_lossy().into_owned();
+    let path = if arg.ends_with(".rs") { PathBuf::from(arg) } else { Path::new("src/bin").join(format!("{}.rs", name)) };
+    (name, path)
+}
+
+fn check(arg: &str) -> Result<bool, String> {
+    let (name, path) = resolve(arg);
+    let source = std::fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    let cases = testcase::extract(&source);
+    if cases.is_empty() {
+        return Err(format!("no `/* testcase: ... */` block in {}", path.display()));
+    }
+    let exe = build(&name)?;
+
+    println!("{} ({} cases)", name, cases.len());
+    println!("{:<6} {:<6} {:>10}", "case", "result", "time");
+    let mut passed = 0;
+    for (i, case) in cases.iter().enumerate() {
+        let (output, time) = run(&exe, &case.input)?;
+        let diff = testcase::diff_tokens(&case.expected, &output);
+        let result = if diff.is_none() { "PASS" } else { "FAIL" };
+        println!("{:<6} {:<6} {:>8.2}ms", i + 1, result, time.as_secs_f64() * 1000.0);
+        match diff {
+            None => passed += 1,
+            Some(m) => print_mismatch(&m, &output),
+        }
+    }
+    println!("{}/{} passed\n", passed, cases.len());
+    Ok(passed == cases.len())
+}
+
+fn build(name: &str) -> Result<PathBuf, String> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = Command::new(cargo)
+        .args(["build", "--quiet", "--release", "--bin", name])
+        .status()
+        .map_err(|e| format!("cannot run cargo: {}", e))?;
+    if !status.success() {
+        return Err(format!("build of --bin {} failed", name));
+    }
+    let target = std::env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".to_string());
+    Ok(Path::new(&target).join("release").join(format!("{}{}", name, std::env::consts::EXE_SUFFIX)))
+}
+
+fn run(exe: &Path, input: &str) -> Result<(String, Duration), String> {
+    let start = Instant::now();
+    let mut child = Command::new(exe)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("cannot start {}: {}", exe.display(), e))?;
+    // write then close stdin so the solution sees eof
+    child.stdin.take().unwrap().write_all(input.as_bytes()).map_err(|e| e.to_string())?;
+    let out = child.wait_with_output().map_err(|e| e.to_string())?;
+    let time = start.elapsed();
+    if !out.status.success() {
+        return Err(format!("{} exited with {}", exe.display(), out.status));
+    }
+    Ok((String::from_utf8_lossy(&out.stdout).into_owned(), time))
+}
+
+fn print_mismatch(m: &Mismatch, output: &str) {
+    let show = |t: &Option<String>| t.clone().unwrap_or_else(|| "<nothing>".to_string());
+    println!("       token {}: expected {}, got {}", m.index + 1, show(&m.expected), show(&m.actual));
+    println!("       output was:");
+    for line in output.lines() {
+        println!("         {}", line);
+    }
+}
@@ -0,0 +1,63 @@
+// runs the leetcode solutions registered in src/leetcode against their sample cases
+// usage (from project root):
+//   cargo run --bin leetcode              (same as `all`)
+//   cargo run --bin leetcode -- list
+//   cargo run --bin leetcode -- 7 8
+
+use rust_files::leetcode::{self, Problem};
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let problems = leetcode::problems();
+
+    if args.first().map(String::as_str) == Some("list") {
+        for p in &problems {
+            println!("{:>4}  {}", p.id, p.name);
+        }
+        return;
+    }
+
+    let selected: Vec<&Problem> = if args.is_empty() || args.iter().any(|a| a == "all") {
+        problems.iter().collect()
+    } else {
+        let mut v = Vec::new();
+        for a in &args {
+            let Ok(id) = a.parse::<u32>() else {
+                eprintln!("not a problem id: {} (try `list`)", a);
+                std::process::exit(2);
+            };
+            let found: Vec<&Problem> = problems.iter().filter(|p| p.id == id).collect();
+            if found.is_empty() {
+                eprintln!("no problem with id {} (try `list`)", id);
+                std::process::exit(2);
+            }
+            v.extend(found);
+        }
+        v
+    };
+
+    let mut failed = 0;
+    for p in selected {
+        let results = p.run();
+        let passed = results.iter().filter(|r| r.passed).count();
+        println!("{:>4}  {:<55} {}/{}", p.id, p.name, passed, results.len());
+        for r in results.iter().filter(|r| !r.passed) {
+            println!("      input {}: expected {}, got {}", r.input, r.expected, r.actual);
+        }
+        failed += results.len() - passed;
+    }
+    if failed > 0 {
+        println!("{} case(s) failed", failed);
+        std::process::exit(1);
+    }
+}
+
+/*
+   3  Longest Substring Without Repeating Characters          6/6
+   3  Longest Substring Without Repeating Characters (window) 6/6
+   7  Reverse Integer                                         7/7
+   7  Reverse Integer (digit vector, cp3)                     7/7
+   7  Reverse Integer (digits::reverse_digits)                7/7
+   8  String to Integer (atoi)                                6/6
+   8  String to Integer (atoi) (digits::saturating_parse)     6/6
+*/
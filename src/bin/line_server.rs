@@ -0,0 +1,45 @@
+// line-protocol tcp server (ECHO, TIME, COUNT, QUIT), see src/line_server.rs
+//   cargo run --bin line_server                                   -> 127.0.0.1:8080
+//   cargo run --bin line_server -- 0.0.0.0:9000 --max-conn 10 --idle 30
+// try it: nc 127.0.0.1 8080  (or: telnet 127.0.0.1 8080), then type ECHO hi / TIME / COUNT / QUIT
+// ctrl-c stops accepting, lets open clients finish their command, says BYE and exits
+
+use std::time::Duration;
+
+use rust_files::line_server::{self, Config};
+use tokio::net::TcpListener;
+
+fn usage() -> ! {
+    eprintln!("usage: line_server [addr] [--max-conn N] [--idle SECS]");
+    std::process::exit(2);
+}
+
+#[tokio::main]
+async fn main() {
+    let mut addr = "127.0.0.1:8080".to_string();
+    let mut config = Config::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--max-conn" => config.max_connections = args.next().and_then(|v| v.parse().ok()).unwrap_or_else(|| usage()),
+            "--idle" => config.idle_timeout = Duration::from_secs(args.next().and_then(|v| v.parse().ok()).unwrap_or_else(|| usage())),
+            a if a.starts_with('-') => usage(),
+            a => addr = a.to_string(),
+        }
+    }
+
+    let listener = match TcpListener::bind(&addr).await {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("line_server: cannot bind {}: {}", addr, e);
+            std::process::exit(1);
+        }
+    };
+    println!("listening on {}", listener.local_addr().unwrap());
+    line_server::serve(listener, config, async {
+        let _ = tokio::signal::ctrl_c().await;
+        println!("shutting down, waiting for open connections");
+    })
+    .await;
+    println!("bye");
+}
@@ -0,0 +1,24 @@
+// local stand-in for https://jsonplaceholder.typicode.com/users, served from src/users/users.json
+//   cargo run --bin mock_api                   -> http://127.0.0.1:8080
+//   cargo run --bin mock_api -- 127.0.0.1:3000
+// then: curl http://127.0.0.1:8080/users/3  or  cargo run --bin 07f_tokio_reqwest_rand_result -- http://127.0.0.1:8080
+
+use rust_files::users::mock;
+use tokio::net::TcpListener;
+
+#[tokio::main]
+async fn main() {
+    let addr = std::env::args().nth(1).unwrap_or_else(|| "127.0.0.1:8080".to_string());
+    let listener = match TcpListener::bind(&addr).await {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("mock_api: cannot bind {}: {}", addr, e);
+            std::process::exit(1);
+        }
+    };
+    println!("serving {} users on http://{}/users", mock::users().len(), listener.local_addr().unwrap());
+    if let Err(e) = mock::serve(listener).await {
+        eprintln!("mock_api: {}", e);
+        std::process::exit(1);
+    }
+}
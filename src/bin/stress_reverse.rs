@@ -0,0 +1,51 @@
+// stress test for leetcode 7. Reverse Integer, see cp2.rs and cp3.rs
+// cp2's i64-widening version is the reference, cp3's second attempt and a buggy i32 version are checked against it
+// both solutions are the ones in src/leetcode, not copies
+
+use rand::Rng;
+use rust_files::leetcode::p0007_reverse_integer::Solution as Cp2; // i64 widening
+use rust_files::leetcode::p0007_reverse_integer_digits::Solution as Cp3; // digit vector + powers of ten
+use rust_files::stress::Stress;
+
+// forgets the overflow check, wraps silently
+fn reverse_wrapping(x: i32) -> i32 {
+    let mut x = x;
+    let mut result: i32 = 0;
+    while x != 0 {
+        result = result.wrapping_mul(10).wrapping_add(x % 10);
+        x /= 10;
+    }
+    result
+}
+
+// mostly small numbers, sometimes anything in i32 range
+fn gen_i32(rng: &mut impl Rng) -> i32 {
+    match rng.gen_range(0..3) {
+        0 => rng.gen_range(-1000..=1000),
+        1 => rng.gen_range(-2_000_000_000..=2_000_000_000),
+        _ => rng.r#gen(),
+    }
+}
+
+fn main() {
+    let stress = Stress::new().cases(10_000).seed(2026);
+
+    match stress.run(gen_i32, |&x| Cp2::reverse(x), |&x| Cp3::reverse(x as i64)) {
+        Ok(n) => println!("cp3 reverse: {} cases passed", n),
+        Err(f) => println!("cp3 reverse: {}", f),
+    }
+    match stress.run(gen_i32, |&x| Cp2::reverse(x), |&x| reverse_wrapping(x)) {
+        Ok(n) => println!("wrapping reverse: {} cases passed", n),
+        Err(f) => println!("wrapping reverse: {}", f),
+    }
+}
+
+// cargo run --bin stress_reverse
+/*
+cp3 reverse: 10000 cases passed
+wrapping reverse: mismatch on case 1 (seed 2027)
+  original input: -2021262477
+  shrunk input:   -1000000003 (15 shrink steps)
+  expected:       0
+  actual:         1294967295
+*/
@@ -1,4 +1,5 @@
 // 2d vector
+// rows here have different lengths on purpose; for a rectangular map (cp problems) use rust_files::grid::Grid, see src/grid.rs
 
 fn main() {
     let row_count = 3;
@@ -1,33 +1,7 @@
-// tle
-// #![allow(unused)]
-struct Solution;
-
-impl Solution {
-    pub fn length_of_longest_substring(s: String) -> i32 {
-        use std::collections::HashMap;
-        let len:usize = s.len();
-        if len == 0 {return 0;}
-        let mut mp: HashMap<char, usize> = HashMap::new();   
-        let mut res:usize = 1;
-        let mut l:usize = 0;
-        let mut r:usize = 0;
-        while r<len{
-            for i in l..len {
-                let c:char = s.chars().nth(i).unwrap();
-                if mp.contains_key(&c){                    
-                    l = mp.get(&c).copied().unwrap()+1;                   
-                    mp.clear();
-                    break;
-                }else{
-                    mp.insert(c, i);                    
-                    r= i+1;
-                    res = std::cmp::max(res, r - l); // 
-                }            
-            }
-        }
-        return res as i32;
-    }
-}
+// tle, see window::longest_unique_substring for the O(n) version (cargo run --release --bin bench_longest_substring)
+// leetcode: 3. Longest Substring Without Repeating Characters
+// the solution moved to src/leetcode/p0003_longest_substring.rs, checked by: cargo run --bin leetcode -- 3
+use rust_files::leetcode::p0003_longest_substring::Solution;
 
 fn main() {
     let v = vec!["abcabcbb", "bbbbb", "pwwkew" ]; // Vec<&str>  
@@ -36,4 +10,4 @@ fn main() {
     }
 }
 
-// rustc "src/bin/test3.rs" --crate-name run_program && .\run_program
\ No newline at end of file
+// cargo run --bin test3
\ No newline at end of file
@@ -0,0 +1,770 @@
+// single-file bundler: a solution from src/bin plus only the library items it uses,
+// so the result can be pasted into codeforces/leetcode or compiled alone with rustc
+//
+// this works on tokens, not on a real parse: the library is split into top-level items,
+// and an item is kept when its name shows up in the solution or in another kept item.
+// that over-approximates (two items with the same name are both kept), which costs bytes, never a build.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Tok {
+    Ident,
+    Punct,
+    Literal, // strings, chars, numbers and lifetimes: never names of items
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Token {
+    kind: Tok,
+    start: usize,
+    end: usize,
+}
+
+fn is_ident_start(b: u8) -> bool {
+    b == b'_' || b.is_ascii_alphabetic()
+}
+
+fn is_ident_char(b: u8) -> bool {
+    b == b'_' || b.is_ascii_alphanumeric()
+}
+
+// end of a "..." string whose opening quote is at i
+fn skip_string(b: &[u8], mut i: usize) -> usize {
+    i += 1;
+    while i < b.len() && b[i] != b'"' {
+        i += if b[i] == b'\\' { 2 } else { 1 };
+    }
+    i + 1
+}
+
+// end of r"..", r#".."#, br#".."# starting at i (just after the r), if it is one
+fn skip_raw_string(b: &[u8], i: usize) -> Option<usize> {
+    let hashes = b[i..].iter().take_while(|&&c| c == b'#').count();
+    if b.get(i + hashes) != Some(&b'"') {
+        return None;
+    }
+    let mut j = i + hashes + 1;
+    while j < b.len() {
+        if b[j] == b'"' && b[j + 1..].iter().take(hashes).filter(|&&c| c == b'#').count() == hashes {
+            return Some(j + 1 + hashes);
+        }
+        j += 1;
+    }
+    Some(b.len())
+}
+
+// 'x', '\n', '\u{1F600}' or 'é' starting at i, None for a lifetime like 'a
+fn skip_char(src: &str, i: usize) -> Option<usize> {
+    let b = src.as_bytes();
+    if b.get(i + 1) == Some(&b'\\') {
+        let mut j = i + 2;
+        while j < b.len() && b[j] != b'\'' {
+            j += 1;
+        }
+        return Some(j + 1);
+    }
+    let c = src[i + 1..].chars().next()?;
+    let j = i + 1 + c.len_utf8();
+    (b.get(j) == Some(&b'\'')).then_some(j + 1)
+}
+
+fn skip_block_comment(b: &[u8], mut i: usize) -> usize {
+    let mut depth = 0;
+    while i < b.len() {
+        if b[i..].starts_with(b"/*") {
+            depth += 1;
+            i += 2;
+        } else if b[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                break;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    i
+}
+
+fn lex(src: &str) -> Vec<Token> {
+    let b = src.as_bytes();
+    let mut toks = Vec::new();
+    let mut i = 0;
+    while i < b.len() {
+        let c = b[i];
+        let start = i;
+        if c.is_ascii_whitespace() || c >= 0x80 {
+            i += 1; // non-ascii only appears inside strings and comments
+            continue;
+        }
+        if b[i..].starts_with(b"//") {
+            while i < b.len() && b[i] != b'\n' {
+                i += 1;
+            }
+            continue;
+        }
+        if b[i..].starts_with(b"/*") {
+            i = skip_block_comment(b, i);
+            continue;
+        }
+        let kind = if c == b'"' {
+            i = skip_string(b, i);
+            Tok::Literal
+        } else if c == b'\'' {
+            match skip_char(src, i) {
+                Some(end) => i = end,
+                None => {
+                    i += 1;
+                    while i < b.len() && is_ident_char(b[i]) {
+                        i += 1;
+                    }
+                }
+            }
+            Tok::Literal
+        } else if c.is_ascii_digit() {
+            while i < b.len() && (is_ident_char(b[i]) || (b[i] == b'.' && b.get(i + 1).is_some_and(u8::is_ascii_digit))) {
+                i += 1;
+            }
+            Tok::Literal
+        } else if is_ident_start(c) {
+            let prefix_end = if b[i..].starts_with(b"br") { i + 2 } else { i + 1 };
+            if (c == b'r' || b[i..].starts_with(b"br")) && let Some(end) = skip_raw_string(b, prefix_end) {
+                i = end;
+                Tok::Literal
+            } else if c == b'b' && b.get(i + 1) == Some(&b'"') {
+                i = skip_string(b, i + 1);
+                Tok::Literal
+            } else if c == b'b' && b.get(i + 1) == Some(&b'\'') {
+                i = skip_char(src, i + 1).unwrap_or(i + 2);
+                Tok::Literal
+            } else {
+                if b[i..].starts_with(b"r#") {
+                    i += 2; // raw identifier r#gen
+                }
+                while i < b.len() && is_ident_char(b[i]) {
+                    i += 1;
+                }
+                Tok::Ident
+            }
+        } else {
+            i += 1;
+            Tok::Punct
+        };
+        toks.push(Token { kind, start, end: i.min(b.len()) });
+    }
+    toks
+}
+
+#[derive(Debug)]
+enum Kind {
+    Plain,
+    Use { glob: bool },
+    Impl { ty: Option<String>, tr: Option<String> },
+    MacroDef { exported: bool, impls: HashSet<String> }, // idents in the `impl .. for ..` lines it generates
+    MacroCall { mac: String },
+    Mod { child: usize },
+}
+
+#[derive(Debug)]
+struct Item {
+    file: usize,
+    start: usize,    // byte offsets in the file, start includes the comments and attributes above
+    end: usize,
+    head_end: usize, // for modules: end of `pub mod name`
+    kind: Kind,
+    names: Vec<String>,
+    refs: Refs,
+    module: usize,
+    cfg_test: bool,
+    kept: bool,
+}
+
+#[derive(Debug)]
+struct Module {
+    path: String, // like "ds::fenwick", empty for the crate root
+    item: Option<usize>,
+    items: Vec<usize>,
+}
+
+struct SourceFile {
+    path: PathBuf,
+    text: String,
+    toks: Vec<Token>,
+}
+
+#[derive(Default)]
+struct Crate {
+    files: Vec<SourceFile>,
+    modules: Vec<Module>,
+    items: Vec<Item>,
+}
+
+/// Why a bundle could not be made.
+#[derive(Debug)]
+pub enum BundleError {
+    Io(PathBuf, io::Error),
+    MissingModule { name: String, from: PathBuf },
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BundleError::Io(path, e) => write!(f, "cannot read {}: {}", path.display(), e),
+            BundleError::MissingModule { name, from } => {
+                write!(f, "module `{}` declared in {} has no file", name, from.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for BundleError {}
+
+/// A solution with the library code it needs appended.
+pub struct Bundle {
+    pub code: String,
+    /// Library modules that made it in, like `scanner` or `heap::one_based`.
+    pub modules: Vec<String>,
+}
+
+impl Crate {
+    fn load(&mut self, path: &Path) -> Result<usize, BundleError> {
+        let text = fs::read_to_string(path).map_err(|e| BundleError::Io(path.to_path_buf(), e))?;
+        let toks = lex(&text);
+        self.files.push(SourceFile { path: path.to_path_buf(), text, toks });
+        Ok(self.files.len() - 1)
+    }
+
+    // splits tokens [lo, hi) of a file into the items of module `module`
+    fn parse_items(&mut self, file: usize, module: usize, lo: usize, hi: usize, text_start: usize) -> Result<(), BundleError> {
+        let (text, toks) = (self.files[file].text.clone(), self.files[file].toks.clone());
+        let tx = |i: usize| -> &str { &text[toks[i].start..toks[i].end] };
+        let at = |i: usize, s: &str| i < hi && tx(i) == s;
+        let mut prev_end = text_start;
+        let mut i = lo;
+        let mut parsed = Vec::new();
+        while i < hi {
+            let mut attrs = String::new();
+            while at(i, "#") {
+                let inner = at(i + 1, "!");
+                let open = if inner { i + 2 } else { i + 1 };
+                let close = close_of(&toks, &text, open);
+                if !inner {
+                    attrs.push_str(&text[toks[open].start..toks[close].end]);
+                }
+                i = close + 1;
+            }
+            if i >= hi {
+                break;
+            }
+            loop {
+                match tx(i) {
+                    "pub" => {
+                        i += 1;
+                        if at(i, "(") {
+                            i = close_of(&toks, &text, i) + 1;
+                        }
+                    }
+                    "unsafe" | "async" | "default" => i += 1,
+                    "extern" if !at(i + 1, "crate") => {
+                        i += 1;
+                        if toks[i].kind == Tok::Literal {
+                            i += 1;
+                        }
+                    }
+                    "const" if matches!(tx(i + 1), "fn" | "unsafe" | "async") => i += 1,
+                    _ => break,
+                }
+            }
+            let kw = tx(i).to_string();
+            let (kind, names, end_tok, head_end) = match kw.as_str() {
+                "use" => {
+                    let end = find_semi(&toks, &text, i);
+                    let (names, glob) = use_names(&toks[i + 1..end], &text);
+                    (Kind::Use { glob }, names, end, 0)
+                }
+                "mod" => {
+                    let name = tx(i + 1).to_string();
+                    let head_end = toks[i + 1].end;
+                    let child = self.modules.len();
+                    let parent_path = &self.modules[module].path;
+                    let path = if parent_path.is_empty() { name.clone() } else { format!("{}::{}", parent_path, name) };
+                    self.modules.push(Module { path, item: None, items: Vec::new() });
+                    if at(i + 2, ";") {
+                        let child_path = self.module_file(file, &name)?;
+                        let child_file = self.load(&child_path)?;
+                        let n = self.files[child_file].toks.len();
+                        self.parse_items(child_file, child, 0, n, 0)?;
+                        (Kind::Mod { child }, vec![name], i + 2, head_end)
+                    } else {
+                        let close = close_of(&toks, &text, i + 2);
+                        self.parse_items(file, child, i + 3, close, toks[i + 2].end)?;
+                        (Kind::Mod { child }, vec![name], close, head_end)
+                    }
+                }
+                "impl" => {
+                    let end = find_item_end(&toks, &text, i);
+                    let (ty, tr) = impl_header(&toks[i + 1..end], &text);
+                    (Kind::Impl { ty, tr }, Vec::new(), end, 0)
+                }
+                "macro_rules" => {
+                    let end = find_item_end(&toks, &text, i);
+                    let exported = attrs.contains("macro_export");
+                    let mut impls = HashSet::new();
+                    for j in (i..end).filter(|&j| tx(j) == "impl") {
+                        let head = (j..end).take_while(|&k| tx(k) != "{").filter(|&k| toks[k].kind == Tok::Ident);
+                        impls.extend(head.map(|k| tx(k).to_string()));
+                    }
+                    (Kind::MacroDef { exported, impls }, vec![tx(i + 2).to_string()], end, 0)
+                }
+                "fn" | "struct" | "enum" | "union" | "trait" | "type" | "const" | "static" => {
+                    let name_at = if at(i + 1, "mut") { i + 2 } else { i + 1 };
+                    let end = find_item_end(&toks, &text, i);
+                    (Kind::Plain, vec![tx(name_at).to_string()], end, 0)
+                }
+                _ => {
+                    // item-level macro call: impl_scan_int!(...); or thread_local! { static X ... }
+                    let end = find_item_end(&toks, &text, i);
+                    let statics = (i..end)
+                        .filter(|&j| tx(j) == "static")
+                        .map(|j| if tx(j + 1) == "mut" { j + 2 } else { j + 1 })
+                        .map(|j| tx(j).to_string())
+                        .collect();
+                    (Kind::MacroCall { mac: kw.clone() }, statics, end, 0)
+                }
+            };
+            let refs = Refs::of(&toks[i..=end_tok], &text);
+            let end = toks[end_tok].end;
+            let cfg_test = attrs.contains("cfg(test)");
+            parsed.push(Item { file, start: prev_end, end, head_end, kind, names, refs, module, cfg_test, kept: false });
+            prev_end = end;
+            i = end_tok + 1;
+        }
+        for item in parsed {
+            if let Kind::Mod { child } = item.kind {
+                self.modules[child].item = Some(self.items.len());
+            }
+            self.modules[module].items.push(self.items.len());
+            self.items.push(item);
+        }
+        Ok(())
+    }
+
+    // where `mod name;` declared in `file` lives: next to lib.rs/mod.rs, or in a folder named after the file
+    fn module_file(&self, file: usize, name: &str) -> Result<PathBuf, BundleError> {
+        let from = &self.files[file].path;
+        let dir = from.parent().unwrap_or(Path::new("."));
+        let stem = from.file_stem().unwrap_or_default();
+        let base = if stem == "lib" || stem == "mod" || stem == "main" { dir.to_path_buf() } else { dir.join(stem) };
+        [base.join(format!("{}.rs", name)), base.join(name).join("mod.rs")]
+            .into_iter()
+            .find(|p| p.exists())
+            .ok_or_else(|| BundleError::MissingModule { name: name.to_string(), from: from.clone() })
+    }
+
+    fn parent_kept(&self, item: &Item) -> bool {
+        self.modules[item.module].item.is_none_or(|m| self.items[m].kept)
+    }
+
+    // marks items reachable from `live` until nothing changes
+    fn mark(&mut self, live: &mut Refs) {
+        let defined: HashSet<String> = self
+            .items
+            .iter()
+            .filter(|it| !matches!(it.kind, Kind::Use { .. }))
+            .flat_map(|it| it.names.iter().cloned())
+            .collect();
+        let is_local_live = |name: &Option<String>, live: &Refs| {
+            name.as_ref().is_none_or(|n| !defined.contains(n) || live.idents.contains(n))
+        };
+        loop {
+            let mut changed = false;
+            for id in 0..self.items.len() {
+                let it = &self.items[id];
+                if it.kept || it.cfg_test {
+                    continue;
+                }
+                let named = it.names.iter().any(|n| live.idents.contains(n));
+                let called = it.names.iter().any(|n| live.macros.contains(n));
+                let keep = match &it.kind {
+                    Kind::MacroDef { exported: true, .. } => called, // lives at the crate root, pulls its module in
+                    _ if !self.parent_kept(it) => false,
+                    Kind::Mod { .. } => it.names.iter().any(|n| live.paths.contains(n)),
+                    Kind::MacroDef { .. } => called,
+                    Kind::Plain => named,
+                    Kind::Use { glob } => *glob || named,
+                    Kind::Impl { ty, tr } => is_local_live(ty, live) && is_local_live(tr, live),
+                    Kind::MacroCall { mac } => match self.macro_def(mac) {
+                        // impl_xxx!(...) is needed as soon as a trait or type it implements is
+                        Some(def) => match &self.items[def].kind {
+                            Kind::MacroDef { impls, .. } => impls.iter().any(|x| defined.contains(x) && live.idents.contains(x)),
+                            _ => false,
+                        },
+                        None => it.names.is_empty() || named,
+                    },
+                };
+                if keep {
+                    changed = true;
+                    let mut c = id;
+                    loop {
+                        self.items[c].kept = true;
+                        live.extend(&self.items[c].refs);
+                        match self.modules[self.items[c].module].item {
+                            Some(m) if !self.items[m].kept => c = m,
+                            _ => break,
+                        }
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    fn macro_def(&self, name: &str) -> Option<usize> {
+        self.items.iter().position(|it| matches!(it.kind, Kind::MacroDef { .. }) && it.names.iter().any(|n| n == name))
+    }
+
+    fn emit(&self, module: usize, out: &mut String, used: &mut Vec<String>) {
+        for &id in &self.modules[module].items {
+            let it = &self.items[id];
+            if !it.kept {
+                continue;
+            }
+            let text = &self.files[it.file].text;
+            match it.kind {
+                Kind::Mod { child } => {
+                    used.push(self.modules[child].path.clone());
+                    if module == 0 {
+                        out.push_str("\n\n#[allow(dead_code, unused_imports, unused_macros)]\n");
+                        out.push_str(text[it.start..it.head_end].trim_start());
+                    } else {
+                        out.push_str(&text[it.start..it.head_end]);
+                    }
+                    out.push_str(" {");
+                    let body = out.len();
+                    self.emit(child, out, used);
+                    if !out[body..].starts_with('\n') {
+                        out.insert(body, '\n'); // a file module starts right at its first line
+                    }
+                    out.push_str("\n}");
+                }
+                _ => out.push_str(&text[it.start..it.end]),
+            }
+        }
+    }
+}
+
+// index of the token closing the group opened at `open`
+fn close_of(toks: &[Token], text: &str, open: usize) -> usize {
+    let mut depth = 0i32;
+    for (j, t) in toks.iter().enumerate().skip(open) {
+        match &text[t.start..t.end] {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => {
+                depth -= 1;
+                if depth == 0 {
+                    return j;
+                }
+            }
+            _ => {}
+        }
+    }
+    toks.len() - 1
+}
+
+fn find_semi(toks: &[Token], text: &str, from: usize) -> usize {
+    (from..toks.len()).find(|&j| &text[toks[j].start..toks[j].end] == ";").unwrap_or(toks.len() - 1)
+}
+
+// last token of an item: a `;` outside any bracket, or the `}` closing its body (plus a `;` right after)
+fn find_item_end(toks: &[Token], text: &str, from: usize) -> usize {
+    let tx = |j: usize| &text[toks[j].start..toks[j].end];
+    let mut depth = 0i32;
+    for j in from..toks.len() {
+        match tx(j) {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" => depth -= 1,
+            "}" => {
+                depth -= 1;
+                if depth == 0 {
+                    return if j + 1 < toks.len() && tx(j + 1) == ";" { j + 1 } else { j };
+                }
+            }
+            ";" if depth == 0 => return j,
+            _ => {}
+        }
+    }
+    toks.len() - 1
+}
+
+/// Names a piece of code refers to.
+#[derive(Debug, Default)]
+struct Refs {
+    idents: HashSet<String>,
+    paths: HashSet<String>,  // idents followed by `::`, the only way a module gets used
+    macros: HashSet<String>, // idents followed by `!`, or imported by a `use`
+}
+
+impl Refs {
+    fn of(toks: &[Token], text: &str) -> Refs {
+        let tx = |j: usize| &text[toks[j].start..toks[j].end];
+        let mut refs = Refs { paths: path_idents(toks, text), ..Refs::default() };
+        let mut in_use = false;
+        for j in 0..toks.len() {
+            if toks[j].kind != Tok::Ident {
+                in_use &= tx(j) != ";";
+                continue;
+            }
+            let name = tx(j).to_string();
+            in_use |= name == "use";
+            let bang = j + 1 < toks.len() && tx(j + 1) == "!" && (j + 2 == toks.len() || tx(j + 2) != "=");
+            if bang || in_use {
+                refs.macros.insert(name.clone());
+            }
+            refs.idents.insert(name);
+        }
+        refs
+    }
+
+    fn extend(&mut self, other: &Refs) {
+        self.idents.extend(other.idents.iter().cloned());
+        self.paths.extend(other.paths.iter().cloned());
+        self.macros.extend(other.macros.iter().cloned());
+    }
+}
+
+// first segments of crate paths (`scanner` in `crate::scanner::Scanner`, `fenwick` in `use fenwick::{..}`),
+// skipping paths into std/core/alloc so `std::ops::Add` doesn't count as a use of our `ops`
+fn path_idents(toks: &[Token], text: &str) -> HashSet<String> {
+    let tx = |j: usize| &text[toks[j].start..toks[j].end];
+    let is_sep = |j: usize| j >= 2 && tx(j - 1) == ":" && tx(j - 2) == ":";
+    let mut found = HashSet::new();
+    for j in 0..toks.len().saturating_sub(2) {
+        if toks[j].kind != Tok::Ident || tx(j + 1) != ":" || tx(j + 2) != ":" {
+            continue;
+        }
+        let mut head = j;
+        loop {
+            if is_sep(head) && head >= 3 && toks[head - 3].kind == Tok::Ident {
+                head -= 3;
+            } else if head >= 1 && matches!(tx(head - 1), "," | "{") {
+                // inside a use tree: jump to the `a::{` this list belongs to
+                let mut depth = 0;
+                let open = (0..head).rev().find(|&k| match tx(k) {
+                    "}" => {
+                        depth += 1;
+                        false
+                    }
+                    "{" if depth > 0 => {
+                        depth -= 1;
+                        false
+                    }
+                    "{" => true,
+                    _ => false,
+                });
+                match open {
+                    Some(k) if is_sep(k) && k >= 3 && toks[k - 3].kind == Tok::Ident => head = k - 3,
+                    _ => break,
+                }
+            } else {
+                break;
+            }
+        }
+        if !matches!(tx(head), "std" | "core" | "alloc") {
+            found.insert(tx(j).to_string());
+        }
+    }
+    found
+}
+
+// names a `use` brings into scope: `a::b::{c, d as e, self}` gives c, e and b
+fn use_names(toks: &[Token], text: &str) -> (Vec<String>, bool) {
+    let tx = |j: usize| &text[toks[j].start..toks[j].end];
+    let mut names = Vec::new();
+    let mut glob = false;
+    for j in 0..toks.len() {
+        let next = if j + 1 < toks.len() { tx(j + 1) } else { ";" };
+        if tx(j) == "*" {
+            glob = true;
+        } else if toks[j].kind == Tok::Ident && matches!(next, "," | "}" | ";") {
+            if tx(j) == "self" {
+                // the segment in front of the `::{` this self sits in
+                let mut depth = 0;
+                let open = (0..j).rev().find(|&k| match tx(k) {
+                    "}" => {
+                        depth += 1;
+                        false
+                    }
+                    "{" if depth > 0 => {
+                        depth -= 1;
+                        false
+                    }
+                    "{" => true,
+                    _ => false,
+                });
+                if let Some(k) = open.filter(|&k| k >= 3) {
+                    names.push(tx(k - 3).to_string());
+                }
+            } else {
+                names.push(tx(j).to_string());
+            }
+        }
+    }
+    (names, glob)
+}
+
+// (self type, trait) of `impl<..> Trait<..> for Type<..> where .. {`, by the last path segment of each
+fn impl_header(toks: &[Token], text: &str) -> (Option<String>, Option<String>) {
+    let tx = |j: usize| &text[toks[j].start..toks[j].end];
+    let mut j = 0;
+    if j < toks.len() && tx(j) == "<" {
+        j = skip_angles(toks, text, j);
+    }
+    let mut angle = 0i32;
+    let (mut before_for, mut after_for, mut seen_for) = (None, None, false);
+    while j < toks.len() {
+        match tx(j) {
+            "{" | "where" if angle == 0 => break,
+            "<" => angle += 1,
+            ">" if j == 0 || tx(j - 1) != "-" => angle -= 1,
+            "for" if angle == 0 => seen_for = true,
+            s if angle == 0 && toks[j].kind == Tok::Ident && !matches!(s, "dyn" | "mut" | "crate" | "self" | "super") => {
+                let slot = if seen_for { &mut after_for } else { &mut before_for };
+                *slot = Some(s.to_string());
+            }
+            _ => {}
+        }
+        j += 1;
+    }
+    if seen_for { (after_for, before_for) } else { (before_for, None) }
+}
+
+fn skip_angles(toks: &[Token], text: &str, open: usize) -> usize {
+    let mut depth = 0;
+    for j in open..toks.len() {
+        match &text[toks[j].start..toks[j].end] {
+            "<" => depth += 1,
+            ">" if text[..toks[j].start].ends_with('-') => {}
+            ">" => {
+                depth -= 1;
+                if depth == 0 {
+                    return j + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    toks.len()
+}
+
+// doc comments carry the doctests, and blank runs are left behind by dropped items
+fn tidy(code: &str) -> String {
+    let mut out = String::new();
+    let mut blank = 0;
+    for line in code.lines() {
+        let t = line.trim_start();
+        if t.starts_with("///") || t.starts_with("//!") {
+            continue;
+        }
+        blank = if t.is_empty() { blank + 1 } else { 0 };
+        if blank <= 1 {
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+    }
+    out
+}
+
+// `use crate::{next, outln, scanner::Scanner};` -> `use crate::{scanner::Scanner};`, and
+// `use crate::next;` disappears; only handles the flat forms solutions actually write
+fn drop_macro_imports(solution: &str, macros: &HashSet<&str>) -> String {
+    let toks = lex(solution);
+    let tx = |j: usize| &solution[toks[j].start..toks[j].end];
+    let mut out = String::new();
+    let mut copied = 0;
+    let mut j = 0;
+    while j + 4 < toks.len() {
+        if !(tx(j) == "use" && tx(j + 1) == "crate" && tx(j + 2) == ":" && tx(j + 3) == ":") {
+            j += 1;
+            continue;
+        }
+        let end = find_semi(&toks, solution, j);
+        let (lo, hi) = if tx(j + 4) == "{" { (j + 5, end - 1) } else { (j + 4, end) };
+        // the entries of the list, split on commas outside nested braces
+        let mut entries = Vec::new();
+        let (mut start, mut depth) = (lo, 0);
+        for k in lo..=hi {
+            if k == hi || (depth == 0 && tx(k) == ",") {
+                if start < k {
+                    entries.push((start, k));
+                }
+                start = k + 1;
+            } else if tx(k) == "{" {
+                depth += 1;
+            } else if tx(k) == "}" {
+                depth -= 1;
+            }
+        }
+        let is_macro = |&(a, b): &(usize, usize)| b == a + 1 && macros.contains(tx(a));
+        if entries.iter().any(is_macro) {
+            let rest: Vec<&str> =
+                entries.iter().filter(|e| !is_macro(e)).map(|&(a, b)| &solution[toks[a].start..toks[b - 1].end]).collect();
+            out.push_str(&solution[copied..toks[j].start]);
+            if !rest.is_empty() {
+                out.push_str(&format!("use crate::{{{}}};", rest.join(", ")));
+            }
+            copied = toks[end].end;
+        }
+        j = end + 1;
+    }
+    out.push_str(&solution[copied..]);
+    out
+}
+
+/// Bundles `solution` (the text of a `src/bin/*.rs` file) with the crate rooted at `lib_rs`.
+/// `rust_files::` paths become `crate::`, and the needed items follow as inline modules.
+///
+/// ```no_run
+/// let src = std::fs::read_to_string("src/bin/04a_cp_input1.rs").unwrap();
+/// let b = rust_files::bundle::bundle(&src, "src/lib.rs".as_ref()).unwrap();
+/// std::fs::write("submit.rs", b.code).unwrap();
+/// ```
+pub fn bundle(solution: &str, lib_rs: &Path) -> Result<Bundle, BundleError> {
+    let mut krate = Crate::default();
+    krate.modules.push(Module { path: String::new(), item: None, items: Vec::new() });
+    let root = krate.load(lib_rs)?;
+    let n = krate.files[root].toks.len();
+    krate.parse_items(root, 0, 0, n, 0)?;
+
+    let solution = solution.replace("rust_files::", "crate::");
+    let mut live = Refs::of(&lex(&solution), &solution);
+    krate.mark(&mut live);
+
+    // #[macro_export] macros now sit at the root of the same crate, importing them again is an error
+    let exported: HashSet<&str> = krate
+        .items
+        .iter()
+        .filter(|it| it.kept && matches!(it.kind, Kind::MacroDef { exported: true, .. }))
+        .flat_map(|it| it.names.iter().map(String::as_str))
+        .collect();
+    let solution = drop_macro_imports(&solution, &exported);
+
+    let mut library = String::new();
+    let mut modules = Vec::new();
+    krate.emit(0, &mut library, &mut modules);
+    let mut code = String::from("// bundled with `cargo run --bin bundle`: the rust_files code used below is inlined at the end\n\n");
+    code.push_str(solution.trim_end());
+    if !library.is_empty() {
+        code.push_str("\n\n// ---------- rust_files (generated, only the items used above) ----------");
+        code.push_str(&tidy(&library));
+    }
+    code.push('\n');
+    Ok(Bundle { code, modules })
+}
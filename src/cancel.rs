@@ -0,0 +1,196 @@
+// timeouts and cancellation around tokio::select!, the pieces 07e's "Timeout!" snippet keeps rewriting:
+//   with_timeout(fut, dur)   -> Err(TimedOut) instead of a printed message
+//   CancelToken              -> one shutdown signal handed to every spawned task (and their children)
+//   race_all(futs)           -> first Ok wins, the others are dropped (= cancelled)
+
+use std::fmt;
+use std::future::{Future, poll_fn};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::task::Poll;
+use std::time::Duration;
+
+use tokio::sync::Notify;
+
+/// `with_timeout` ran out of time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimedOut {
+    pub after: Duration,
+}
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "timed out after {:?}", self.after)
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// `fut`'s output, or `TimedOut` if it takes longer than `dur` (`fut` is dropped then).
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use std::time::Duration;
+/// use rust_files::cancel::{with_timeout, TimedOut};
+/// let slow = tokio::time::sleep(Duration::from_secs(5));
+/// let r = with_timeout(slow, Duration::from_millis(10)).await;
+/// assert_eq!(r, Err(TimedOut { after: Duration::from_millis(10) }));
+/// # }
+/// ```
+pub async fn with_timeout<F: Future>(fut: F, dur: Duration) -> Result<F::Output, TimedOut> {
+    tokio::time::timeout(dur, fut).await.map_err(|_| TimedOut { after: dur })
+}
+
+struct Inner {
+    cancelled: AtomicBool,
+    notify: Notify,
+    children: Mutex<Vec<Weak<Inner>>>,
+}
+
+impl Inner {
+    fn new(cancelled: bool) -> Arc<Inner> {
+        Arc::new(Inner { cancelled: AtomicBool::new(cancelled), notify: Notify::new(), children: Mutex::new(Vec::new()) })
+    }
+
+    fn cancel(&self) {
+        if self.cancelled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.notify.notify_waiters();
+        let children = std::mem::take(&mut *self.children.lock().unwrap());
+        for child in children.iter().filter_map(Weak::upgrade) {
+            child.cancel();
+        }
+    }
+}
+
+/// Shutdown signal shared by clones. Cancelling a token cancels its clones and its
+/// [`child`](CancelToken::child) tokens, never its parent.
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use rust_files::cancel::CancelToken;
+/// let token = CancelToken::new();
+/// let job = tokio::spawn({
+///     let token = token.child();
+///     async move {
+///         let mut rounds = 0;
+///         while !token.is_cancelled() {
+///             rounds += 1;
+///             tokio::task::yield_now().await;
+///         }
+///         rounds
+///     }
+/// });
+/// tokio::task::yield_now().await;
+/// token.cancel();
+/// assert!(job.await.unwrap() > 0);
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct CancelToken {
+    inner: Arc<Inner>,
+}
+
+impl Default for CancelToken {
+    fn default() -> Self {
+        CancelToken::new()
+    }
+}
+
+impl fmt::Debug for CancelToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CancelToken").field("cancelled", &self.is_cancelled()).finish()
+    }
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        CancelToken { inner: Inner::new(false) }
+    }
+
+    /// A token that is cancelled with this one, but can also be cancelled on its own,
+    /// e.g. to stop one task of a group.
+    pub fn child(&self) -> CancelToken {
+        let mut children = self.inner.children.lock().unwrap();
+        let child = Inner::new(self.is_cancelled());
+        children.retain(|c| c.strong_count() > 0);
+        children.push(Arc::downgrade(&child));
+        CancelToken { inner: child }
+    }
+
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Completes once the token is cancelled (right away if it already is).
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable(); // registered before the check, so a cancel in between is not missed
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Runs `fut` until it finishes (`Some`) or the token is cancelled (`None`, `fut` is dropped).
+    pub async fn run_until_cancelled<F: Future>(&self, fut: F) -> Option<F::Output> {
+        tokio::select! {
+            biased;
+            _ = self.cancelled() => None,
+            out = fut => Some(out),
+        }
+    }
+}
+
+/// Polls all `futs` together and returns the first `Ok`; the rest are dropped, which cancels them.
+/// If every one fails, all errors come back in input order (none at all for no futures).
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use std::time::Duration;
+/// use rust_files::cancel::race_all;
+/// let mirror = |ms: u64, ok: bool| async move {
+///     tokio::time::sleep(Duration::from_millis(ms)).await;
+///     if ok { Ok(ms) } else { Err(ms) }
+/// };
+/// assert_eq!(race_all(vec![mirror(30, true), mirror(10, false), mirror(20, true)]).await, Ok(20));
+/// assert_eq!(race_all(vec![mirror(3, false), mirror(1, false)]).await, Err(vec![3, 1]));
+/// # }
+/// ```
+pub async fn race_all<T, E, F>(futs: impl IntoIterator<Item = F>) -> Result<T, Vec<E>>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    let mut running: Vec<Option<Pin<Box<F>>>> = futs.into_iter().map(|f| Some(Box::pin(f))).collect();
+    let mut errors: Vec<Option<E>> = running.iter().map(|_| None).collect();
+    let mut left = running.len();
+    poll_fn(|cx| {
+        for (slot, err) in running.iter_mut().zip(errors.iter_mut()) {
+            let Some(fut) = slot else { continue };
+            if let Poll::Ready(res) = fut.as_mut().poll(cx) {
+                *slot = None;
+                match res {
+                    Ok(v) => return Poll::Ready(Ok(v)),
+                    Err(e) => {
+                        *err = Some(e);
+                        left -= 1;
+                    }
+                }
+            }
+        }
+        if left == 0 { Poll::Ready(Err(errors.drain(..).flatten().collect())) } else { Poll::Pending }
+    })
+    .await
+}
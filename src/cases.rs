@@ -0,0 +1,67 @@
+// the `T` test cases loop every codeforces solution starts with, written once
+// build with `--features debug` to get the time of every case on stderr
+
+use std::io::{Read, StdinLock, StdoutLock, Write};
+
+use crate::output::Output;
+use crate::scanner::Scanner;
+
+/// Runs `solve` once per test case, sharing one scanner and one writer.
+/// With `multi` the case count `T` is read first, otherwise there is exactly one case.
+///
+/// ```
+/// use rust_files::{cases, outln};
+/// use rust_files::output::Output;
+/// use rust_files::scanner::Scanner;
+/// let mut sc = Scanner::new("2\n3 4\n10 20\n".as_bytes());
+/// let mut buf = Vec::new();
+/// cases::run(&mut sc, &mut Output::new(&mut buf), true, |sc, out| {
+///     let (a, b): (i64, i64) = sc.next();
+///     outln!(out, "{}", a + b);
+/// });
+/// assert_eq!(String::from_utf8(buf).unwrap(), "7\n30\n");
+/// ```
+pub fn run<R, W, F>(sc: &mut Scanner<R>, out: &mut Output<W>, multi: bool, mut solve: F)
+where
+    R: Read,
+    W: Write,
+    F: FnMut(&mut Scanner<R>, &mut Output<W>),
+{
+    let t: usize = if multi { sc.next() } else { 1 };
+    #[cfg(feature = "debug")]
+    let start = std::time::Instant::now();
+    for _case in 1..=t {
+        #[cfg(feature = "debug")]
+        let case_start = std::time::Instant::now();
+        solve(sc, out);
+        #[cfg(feature = "debug")]
+        eprintln!("case {}/{}: {:?}", _case, t, case_start.elapsed());
+    }
+    #[cfg(feature = "debug")]
+    eprintln!("total: {:?}", start.elapsed());
+}
+
+/// [`run`] over locked stdin/stdout, this is what [`solve_cases!`](crate::solve_cases) expands to.
+pub fn run_stdio<F>(multi: bool, solve: F)
+where
+    F: FnMut(&mut Scanner<StdinLock<'static>>, &mut Output<StdoutLock<'static>>),
+{
+    let mut sc = Scanner::new(std::io::stdin().lock());
+    let mut out = Output::stdout();
+    run(&mut sc, &mut out, multi, solve);
+}
+
+/// Entry point of a solution: `fn main() { solve_cases!(solve); }` reads `T` and calls
+/// `solve(&mut sc, &mut out)` that many times; `solve_cases!(single, solve)` calls it once.
+///
+/// `solve` is usually written generic so tests can feed it a byte slice:
+/// `fn solve<R: Read, W: Write>(sc: &mut Scanner<R>, out: &mut Output<W>)`.
+#[macro_export]
+macro_rules! solve_cases {
+    (single, $solve:expr) => {
+        $crate::cases::run_stdio(false, $solve)
+    };
+    ($solve:expr) => {
+        $crate::cases::run_stdio(true, $solve)
+    };
+}
@@ -0,0 +1,162 @@
+// digit level helpers for every primitive integer: checked/saturating parsing, digit iteration,
+// digit reversal and base conversion. generalises my_atoi (cp1) and reverse (cp2/cp3).
+
+use std::fmt::Debug;
+
+/// The primitive integer types (`i8`..`i128`, `u8`..`u128`, `isize`, `usize`).
+pub trait PrimInt: Copy + Ord + Debug {
+    const ZERO: Self;
+    const MIN: Self;
+    const MAX: Self;
+    const SIGNED: bool;
+    fn from_digit(d: u8) -> Self; // d < 36 fits every type
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    /// `(self / base, |self % base|)`, truncating like `/` and `%`.
+    fn div_rem_digit(self, base: u32) -> (Self, u8);
+}
+
+macro_rules! impl_prim_int {
+    ($signed:expr, $($t:ty),*) => {$(
+        impl PrimInt for $t {
+            const ZERO: Self = 0;
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+            const SIGNED: bool = $signed;
+            fn from_digit(d: u8) -> Self { d as Self }
+            fn checked_add(self, rhs: Self) -> Option<Self> { <$t>::checked_add(self, rhs) }
+            fn checked_sub(self, rhs: Self) -> Option<Self> { <$t>::checked_sub(self, rhs) }
+            fn checked_mul(self, rhs: Self) -> Option<Self> { <$t>::checked_mul(self, rhs) }
+            #[allow(unused_comparisons)]
+            fn div_rem_digit(self, base: u32) -> (Self, u8) {
+                let b = base as Self;
+                let r = self % b;
+                (self / b, if r < 0 { (0 - r) as u8 } else { r as u8 })
+            }
+        }
+    )*};
+}
+
+impl_prim_int!(false, u8, u16, u32, u64, u128, usize);
+impl_prim_int!(true, i8, i16, i32, i64, i128, isize);
+
+// shared digit loop: accumulates toward the sign so MIN parses without overflow.
+// stops at the first non-digit, returns the value (None on overflow) and how many digits were used
+fn accumulate<T: PrimInt>(digits: &[u8], neg: bool, base: u32) -> (Option<T>, usize) {
+    let b = T::from_digit(base as u8);
+    let mut n = Some(T::ZERO);
+    let mut used = 0;
+    for &c in digits {
+        let Some(d) = (c as char).to_digit(base) else { break };
+        let d = T::from_digit(d as u8);
+        n = n.and_then(|n| n.checked_mul(b)).and_then(|n| if neg { n.checked_sub(d) } else { n.checked_add(d) });
+        used += 1;
+    }
+    (n, used)
+}
+
+fn split_sign(s: &[u8]) -> (bool, &[u8]) {
+    match s.first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    }
+}
+
+/// Strict parse of bytes in base `base`: optional sign then digits only, `None` on anything else
+/// or on overflow. Same rules as `T::from_str_radix` (so `"-0"` is rejected for unsigned types).
+pub fn parse_bytes_radix<T: PrimInt>(s: &[u8], base: u32) -> Option<T> {
+    assert!((2..=36).contains(&base), "base must be in 2..=36");
+    let (neg, digits) = split_sign(s);
+    if digits.is_empty() || (neg && !T::SIGNED) {
+        return None;
+    }
+    match accumulate(digits, neg, base) {
+        (Some(n), used) if used == digits.len() => Some(n),
+        _ => None,
+    }
+}
+
+/// Strict decimal parse, agrees with `s.parse::<T>().ok()`.
+pub fn checked_parse<T: PrimInt>(s: &str) -> Option<T> {
+    parse_bytes_radix(s.as_bytes(), 10)
+}
+
+/// Parse in any base 2..=36, agrees with `T::from_str_radix(s, base).ok()`.
+pub fn from_base<T: PrimInt>(s: &str, base: u32) -> Option<T> {
+    parse_bytes_radix(s.as_bytes(), base)
+}
+
+/// atoi rules for any width: skip leading spaces, optional sign, read digits until the first
+/// non-digit, clamp to `T::MIN..=T::MAX`. No digits gives 0. `saturating_parse::<i32>` is leetcode 8.
+pub fn saturating_parse<T: PrimInt>(s: &str) -> T {
+    let (neg, digits) = split_sign(s.trim_start_matches(' ').as_bytes());
+    if neg && !T::SIGNED {
+        return T::ZERO; // any negative number clamps to 0
+    }
+    match accumulate(digits, neg, 10) {
+        (Some(n), _) => n,
+        (None, _) if neg => T::MIN,
+        (None, _) => T::MAX,
+    }
+}
+
+/// Decimal digits of `|n|`, least significant first. `0` gives `[0]`.
+pub fn digits_rev<T: PrimInt>(n: T) -> DigitsRev<T> {
+    DigitsRev { n, base: 10, first: true }
+}
+
+/// Decimal digits of `|n|`, most significant first.
+pub fn digits<T: PrimInt>(n: T) -> Vec<u8> {
+    let mut v: Vec<u8> = digits_rev(n).collect();
+    v.reverse();
+    v
+}
+
+/// Iterator from [`digits_rev`].
+pub struct DigitsRev<T> {
+    n: T,
+    base: u32,
+    first: bool,
+}
+
+impl<T: PrimInt> Iterator for DigitsRev<T> {
+    type Item = u8;
+    fn next(&mut self) -> Option<u8> {
+        if self.n == T::ZERO && !self.first {
+            return None;
+        }
+        self.first = false;
+        let (q, d) = self.n.div_rem_digit(self.base);
+        self.n = q;
+        Some(d)
+    }
+}
+
+/// Reverses the decimal digits keeping the sign, `None` if the result doesn't fit in `T`
+/// (`reverse_digits(1534236469i32) == None`). Trailing zeros vanish: 120 -> 21.
+pub fn reverse_digits<T: PrimInt>(n: T) -> Option<T> {
+    let neg = n < T::ZERO;
+    let ten = T::from_digit(10);
+    let mut res = T::ZERO;
+    for d in digits_rev(n) {
+        let d = T::from_digit(d);
+        res = res.checked_mul(ten)?;
+        res = if neg { res.checked_sub(d)? } else { res.checked_add(d)? };
+    }
+    Some(res)
+}
+
+/// `n` written in base 2..=36 with lowercase letters, `-` in front for negatives.
+pub fn to_base<T: PrimInt>(n: T, base: u32) -> String {
+    assert!((2..=36).contains(&base), "base must be in 2..=36");
+    let mut s: Vec<u8> = DigitsRev { n, base, first: true }
+        .map(|d| std::char::from_digit(d as u32, base).unwrap() as u8)
+        .collect();
+    if n < T::ZERO {
+        s.push(b'-');
+    }
+    s.reverse();
+    String::from_utf8(s).unwrap()
+}
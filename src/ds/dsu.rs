@@ -0,0 +1,93 @@
+// disjoint-set union: union by rank + path compression, near O(1) per operation
+
+/// Disjoint sets over `0..n`.
+///
+/// ```
+/// use rust_files::ds::Dsu;
+/// let mut d = Dsu::new(4);
+/// assert!(d.union(0, 1));
+/// assert!(!d.union(1, 0)); // already together
+/// assert!(d.same(0, 1) && !d.same(0, 2));
+/// assert_eq!((d.size(1), d.count()), (2, 3));
+/// ```
+pub struct Dsu {
+    parent: Vec<usize>,
+    rank: Vec<u8>, // upper bound on tree height, stays below 64
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl Dsu {
+    pub fn new(n: usize) -> Self {
+        Dsu { parent: (0..n).collect(), rank: vec![0; n], size: vec![1; n], count: n }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Representative of the set containing `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // second pass points everything on the way straight at the root (no recursion)
+        let mut x = x;
+        while self.parent[x] != root {
+            let next = self.parent[x];
+            self.parent[x] = root;
+            x = next;
+        }
+        root
+    }
+
+    /// Merges the sets of `a` and `b`, false if they were already one set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.rank[a] < self.rank[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        if self.rank[a] == self.rank[b] {
+            self.rank[a] += 1;
+        }
+        self.count -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Number of elements in the set containing `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let r = self.find(x);
+        self.size[r]
+    }
+
+    /// Number of disjoint sets.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// All sets, each sorted, in order of their smallest element.
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut by_root: Vec<Vec<usize>> = vec![Vec::new(); self.len()];
+        for x in 0..self.len() {
+            let r = self.find(x);
+            by_root[r].push(x);
+        }
+        let mut groups: Vec<Vec<usize>> = by_root.into_iter().filter(|g| !g.is_empty()).collect();
+        groups.sort_unstable_by_key(|g| g[0]);
+        groups
+    }
+}
@@ -0,0 +1,105 @@
+// fenwick (binary indexed) trees: O(log n) point update + prefix query, and the range-update twin
+
+use std::marker::PhantomData;
+
+use crate::ops::{Group, Sum};
+
+/// Point update / prefix query over an invertible operation (sum by default, xor works too).
+///
+/// ```
+/// use rust_files::ds::Fenwick;
+/// let mut f = Fenwick::<i64>::new(5);
+/// f.add(1, 3);
+/// f.add(3, 4);
+/// assert_eq!(f.prefix(3), 3);
+/// assert_eq!(f.range(1, 5), 7);
+/// ```
+pub struct Fenwick<T, O = Sum> {
+    tree: Vec<T>, // 1-indexed internally, tree[0] unused
+    _op: PhantomData<O>,
+}
+
+impl<T: Clone, O: Group<T>> Fenwick<T, O> {
+    /// `n` elements, all the identity (0 for sums).
+    pub fn new(n: usize) -> Self {
+        Fenwick { tree: vec![O::id(); n + 1], _op: PhantomData }
+    }
+
+    /// Builds from a slice in O(n).
+    pub fn from_slice(a: &[T]) -> Self {
+        let mut tree = vec![O::id()];
+        tree.extend_from_slice(a);
+        for i in 1..tree.len() {
+            let j = i + (i & i.wrapping_neg());
+            if j < tree.len() {
+                tree[j] = O::op(&tree[j], &tree[i]);
+            }
+        }
+        Fenwick { tree, _op: PhantomData }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `a[i] = op(a[i], v)`, i.e. `a[i] += v` for sums. 0-indexed.
+    pub fn add(&mut self, i: usize, v: T) {
+        let mut i = i + 1;
+        while i < self.tree.len() {
+            self.tree[i] = O::op(&self.tree[i], &v);
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Fold of `a[..i]`.
+    pub fn prefix(&self, i: usize) -> T {
+        let mut i = i;
+        let mut res = O::id();
+        while i > 0 {
+            res = O::op(&res, &self.tree[i]);
+            i -= i & i.wrapping_neg();
+        }
+        res
+    }
+
+    /// Fold of `a[l..r]`.
+    pub fn range(&self, l: usize, r: usize) -> T {
+        O::inv_op(&self.prefix(r), &self.prefix(l))
+    }
+}
+
+/// Range update / point query: a fenwick over the difference array.
+/// For sums `T` must allow negatives (i64, not u64), since the update is undone at `r`.
+pub struct RangeFenwick<T, O = Sum> {
+    diff: Fenwick<T, O>,
+}
+
+impl<T: Clone, O: Group<T>> RangeFenwick<T, O> {
+    pub fn new(n: usize) -> Self {
+        RangeFenwick { diff: Fenwick::new(n + 1) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.diff.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `a[i] = op(a[i], v)` for every `i` in `l..r`.
+    pub fn range_add(&mut self, l: usize, r: usize, v: T) {
+        assert!(l <= r && r <= self.len(), "bad range {}..{}", l, r);
+        self.diff.add(r, O::inv_op(&O::id(), &v));
+        self.diff.add(l, v);
+    }
+
+    /// Current value of `a[i]`.
+    pub fn get(&self, i: usize) -> T {
+        self.diff.prefix(i + 1)
+    }
+}
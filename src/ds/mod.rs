@@ -0,0 +1,9 @@
+// data structures for range problems, built on the operations in ops.rs, plus dsu for graphs
+
+pub mod dsu;
+pub mod fenwick;
+pub mod segtree;
+
+pub use dsu::Dsu;
+pub use fenwick::{Fenwick, RangeFenwick};
+pub use segtree::{Action, AddMinMax, AddSum, Assign, LazySegTree, NoAction, SegTree};
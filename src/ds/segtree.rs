@@ -0,0 +1,229 @@
+// segment tree with lazy propagation
+// values are combined by a Monoid (ops.rs), range updates are an Action applied lazily
+
+use std::marker::PhantomData;
+use std::ops::{Add, Mul};
+
+use crate::digits::PrimInt;
+use crate::ops::Monoid;
+
+/// A range update: a map `F` applied to segment values, with composition so pending updates stack.
+pub trait Action<T> {
+    type F: Clone;
+    /// The map that changes nothing.
+    fn id_map() -> Self::F;
+    /// `f` applied to the folded value of a segment of `len` elements.
+    fn apply(f: &Self::F, x: &T, len: usize) -> T;
+    /// `f` after `g` (g was pending first).
+    fn compose(f: &Self::F, g: &Self::F) -> Self::F;
+}
+
+/// No range updates: a plain segment tree with point `set`.
+pub struct NoAction;
+
+impl<T: Clone> Action<T> for NoAction {
+    type F = ();
+    fn id_map() {}
+    fn apply(_: &(), x: &T, _: usize) -> T {
+        x.clone()
+    }
+    fn compose(_: &(), _: &()) {}
+}
+
+/// Range add for a `Sum` tree: a segment of `len` elements grows by `v * len`.
+pub struct AddSum;
+
+impl<T> Action<T> for AddSum
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + TryFrom<usize>,
+{
+    type F = T;
+    fn id_map() -> T {
+        T::default()
+    }
+    fn apply(f: &T, x: &T, len: usize) -> T {
+        let len = T::try_from(len).ok().expect("segment length doesn't fit the value type");
+        *x + *f * len
+    }
+    fn compose(f: &T, g: &T) -> T {
+        *f + *g
+    }
+}
+
+/// Range add for a `Min` or `Max` tree: the extreme moves by `v`.
+/// The identity (`MAX` for `Min`, `MIN` for `Max`) absorbs it, so leaves that were never set
+/// stay the identity.
+pub struct AddMinMax;
+
+impl<T: PrimInt + Add<Output = T>> Action<T> for AddMinMax {
+    type F = T;
+    fn id_map() -> T {
+        T::ZERO
+    }
+    fn apply(f: &T, x: &T, _: usize) -> T {
+        if *x == T::MAX || *x == T::MIN { *x } else { *x + *f }
+    }
+    fn compose(f: &T, g: &T) -> T {
+        f.checked_add(*g).expect("pending range adds overflow")
+    }
+}
+
+/// Range assign for a `Min`, `Max` (or any idempotent) tree: every element becomes `v`.
+pub struct Assign;
+
+impl<T: Clone> Action<T> for Assign {
+    type F = Option<T>;
+    fn id_map() -> Option<T> {
+        None
+    }
+    fn apply(f: &Option<T>, x: &T, _: usize) -> T {
+        f.clone().unwrap_or_else(|| x.clone())
+    }
+    fn compose(f: &Option<T>, g: &Option<T>) -> Option<T> {
+        f.clone().or_else(|| g.clone())
+    }
+}
+
+/// Segment tree without range updates.
+pub type SegTree<T, M> = LazySegTree<T, M, NoAction>;
+
+/// O(log n) range fold, point set and lazy range update.
+///
+/// ```
+/// use rust_files::ds::{AddSum, LazySegTree};
+/// use rust_files::ops::Sum;
+/// let mut st = LazySegTree::<i64, Sum, AddSum>::from_slice(&[1, 2, 3, 4]);
+/// st.apply(1, 3, 10); // a = [1, 12, 13, 4]
+/// assert_eq!(st.query(0, 4), 30);
+/// assert_eq!(st.get(2), 13);
+/// ```
+pub struct LazySegTree<T, M, A: Action<T>> {
+    n: usize,
+    tree: Vec<T>,
+    lazy: Vec<A::F>,
+    _m: PhantomData<M>,
+}
+
+impl<T: Clone, M: Monoid<T>, A: Action<T>> LazySegTree<T, M, A> {
+    /// `n` elements, all the identity.
+    pub fn new(n: usize) -> Self {
+        Self::from_slice(&vec![M::id(); n])
+    }
+
+    pub fn from_slice(a: &[T]) -> Self {
+        let n = a.len();
+        let size = 4 * n.max(1);
+        let mut st = LazySegTree { n, tree: vec![M::id(); size], lazy: vec![A::id_map(); size], _m: PhantomData };
+        if n > 0 {
+            st.build(1, 0, n, a);
+        }
+        st
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    fn build(&mut self, node: usize, lo: usize, hi: usize, a: &[T]) {
+        if hi - lo == 1 {
+            self.tree[node] = a[lo].clone();
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        self.build(2 * node, lo, mid, a);
+        self.build(2 * node + 1, mid, hi, a);
+        self.pull(node);
+    }
+
+    fn pull(&mut self, node: usize) {
+        self.tree[node] = M::op(&self.tree[2 * node], &self.tree[2 * node + 1]);
+    }
+
+    // applies f to a whole node and remembers it for the children
+    fn apply_node(&mut self, node: usize, len: usize, f: &A::F) {
+        self.tree[node] = A::apply(f, &self.tree[node], len);
+        self.lazy[node] = A::compose(f, &self.lazy[node]);
+    }
+
+    fn push(&mut self, node: usize, lo: usize, hi: usize) {
+        let f = std::mem::replace(&mut self.lazy[node], A::id_map());
+        let mid = (lo + hi) / 2;
+        self.apply_node(2 * node, mid - lo, &f);
+        self.apply_node(2 * node + 1, hi - mid, &f);
+    }
+
+    /// Fold of `a[l..r]`, the identity for an empty range.
+    pub fn query(&mut self, l: usize, r: usize) -> T {
+        assert!(l <= r && r <= self.n, "bad range {}..{}", l, r);
+        if l == r {
+            return M::id();
+        }
+        self.query_rec(1, 0, self.n, l, r)
+    }
+
+    fn query_rec(&mut self, node: usize, lo: usize, hi: usize, l: usize, r: usize) -> T {
+        if r <= lo || hi <= l {
+            return M::id();
+        }
+        if l <= lo && hi <= r {
+            return self.tree[node].clone();
+        }
+        self.push(node, lo, hi);
+        let mid = (lo + hi) / 2;
+        let left = self.query_rec(2 * node, lo, mid, l, r);
+        let right = self.query_rec(2 * node + 1, mid, hi, l, r);
+        M::op(&left, &right)
+    }
+
+    /// Applies `f` to every element of `a[l..r]`.
+    pub fn apply(&mut self, l: usize, r: usize, f: A::F) {
+        assert!(l <= r && r <= self.n, "bad range {}..{}", l, r);
+        if l < r {
+            self.apply_rec(1, 0, self.n, l, r, &f);
+        }
+    }
+
+    fn apply_rec(&mut self, node: usize, lo: usize, hi: usize, l: usize, r: usize, f: &A::F) {
+        if r <= lo || hi <= l {
+            return;
+        }
+        if l <= lo && hi <= r {
+            self.apply_node(node, hi - lo, f);
+            return;
+        }
+        self.push(node, lo, hi);
+        let mid = (lo + hi) / 2;
+        self.apply_rec(2 * node, lo, mid, l, r, f);
+        self.apply_rec(2 * node + 1, mid, hi, l, r, f);
+        self.pull(node);
+    }
+
+    /// `a[i] = v`.
+    pub fn set(&mut self, i: usize, v: T) {
+        assert!(i < self.n, "index {} out of range", i);
+        self.set_rec(1, 0, self.n, i, v);
+    }
+
+    fn set_rec(&mut self, node: usize, lo: usize, hi: usize, i: usize, v: T) {
+        if hi - lo == 1 {
+            self.tree[node] = v;
+            return;
+        }
+        self.push(node, lo, hi);
+        let mid = (lo + hi) / 2;
+        if i < mid {
+            self.set_rec(2 * node, lo, mid, i, v);
+        } else {
+            self.set_rec(2 * node + 1, mid, hi, i, v);
+        }
+        self.pull(node);
+    }
+
+    pub fn get(&mut self, i: usize) -> T {
+        self.query(i, i + 1)
+    }
+}
@@ -0,0 +1,149 @@
+// run one async job per id, at most `concurrency` at a time, results back in input order
+// (07c awaits one after the other, 07d joins a fixed handful; this is the version for a list of ids)
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+
+/// Why one item of a batch has no value. The rest of the batch is not affected.
+#[derive(Debug, PartialEq)]
+pub enum FetchError<E> {
+    /// The job returned `Err`.
+    Failed(E),
+    /// The job panicked.
+    Panicked,
+    /// Not done when the batch deadline passed; the job was cancelled.
+    DeadlineExceeded,
+}
+
+impl<E: fmt::Display> fmt::Display for FetchError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::Failed(e) => write!(f, "{}", e),
+            FetchError::Panicked => write!(f, "job panicked"),
+            FetchError::DeadlineExceeded => write!(f, "deadline exceeded"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for FetchError<E> {}
+
+/// Runs `job(id)` for every id with at most `concurrency` (at least 1) running at once.
+/// `result[i]` belongs to the i-th id; a failing or panicking job only fails its own slot.
+/// Dropping the returned future cancels the jobs that are still running.
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use rust_files::fanout::{fetch_many, FetchError};
+/// let res = fetch_many(vec![1, 2, 0, 4], 2, |id: u32| async move {
+///     if id == 0 { Err("no id 0") } else { Ok(format!("Data for id {}", id)) }
+/// })
+/// .await;
+/// assert_eq!(res[1], Ok("Data for id 2".to_string()));
+/// assert_eq!(res[2], Err(FetchError::Failed("no id 0")));
+/// # }
+/// ```
+pub async fn fetch_many<K, T, E, F, Fut>(
+    ids: impl IntoIterator<Item = K>,
+    concurrency: usize,
+    job: F,
+) -> Vec<Result<T, FetchError<E>>>
+where
+    K: Send + 'static,
+    T: Send + 'static,
+    E: Send + 'static,
+    F: Fn(K) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<T, E>> + Send + 'static,
+{
+    run(ids, concurrency, None, job).await
+}
+
+/// [`fetch_many`] with a deadline for the whole batch: whatever is not finished `deadline`
+/// after the call (still running or still waiting for a slot) is cancelled and reported as
+/// [`FetchError::DeadlineExceeded`]. The call itself returns by then.
+pub async fn fetch_many_with_deadline<K, T, E, F, Fut>(
+    ids: impl IntoIterator<Item = K>,
+    concurrency: usize,
+    deadline: Duration,
+    job: F,
+) -> Vec<Result<T, FetchError<E>>>
+where
+    K: Send + 'static,
+    T: Send + 'static,
+    E: Send + 'static,
+    F: Fn(K) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<T, E>> + Send + 'static,
+{
+    run(ids, concurrency, Some(Instant::now() + deadline), job).await
+}
+
+async fn run<K, T, E, F, Fut>(
+    ids: impl IntoIterator<Item = K>,
+    concurrency: usize,
+    deadline: Option<Instant>,
+    job: F,
+) -> Vec<Result<T, FetchError<E>>>
+where
+    K: Send + 'static,
+    T: Send + 'static,
+    E: Send + 'static,
+    F: Fn(K) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<T, E>> + Send + 'static,
+{
+    let permits = Arc::new(Semaphore::new(concurrency.max(1)));
+    let job = Arc::new(job);
+    // the JoinSet aborts whatever is left when it's dropped, so dropping this future (or
+    // hitting the deadline) cancels the jobs instead of leaving them running
+    let mut tasks = JoinSet::new();
+    let mut slot_of = HashMap::new();
+    let mut results: Vec<Option<Result<T, FetchError<E>>>> = Vec::new();
+    let mut ids = ids.into_iter();
+
+    // a task is only spawned once it has a slot, so at most `concurrency` exist at a time
+    for id in ids.by_ref() {
+        results.push(None);
+        let acquire = permits.clone().acquire_owned();
+        let permit = match deadline {
+            None => acquire.await,
+            Some(at) => match tokio::time::timeout_at(at, acquire).await {
+                Ok(permit) => permit,
+                Err(_) => break,
+            },
+        }
+        .expect("semaphore is never closed");
+        let job = job.clone();
+        let handle = tasks.spawn(async move {
+            let _permit = permit;
+            job(id).await
+        });
+        slot_of.insert(handle.id(), results.len() - 1);
+    }
+    // ids that never got a slot before the deadline
+    results.extend(ids.map(|_| None));
+
+    loop {
+        let next = match deadline {
+            None => tasks.join_next_with_id().await,
+            // a task that already finished still counts, timeout_at polls it before the clock
+            Some(at) => match tokio::time::timeout_at(at, tasks.join_next_with_id()).await {
+                Ok(next) => next,
+                Err(_) => break,
+            },
+        };
+        let Some(joined) = next else { break };
+        let (task, res) = match joined {
+            Ok((task, Ok(v))) => (task, Ok(v)),
+            Ok((task, Err(e))) => (task, Err(FetchError::Failed(e))),
+            Err(e) => (e.id(), Err(FetchError::Panicked)),
+        };
+        results[slot_of[&task]] = Some(res);
+    }
+    results.into_iter().map(|r| r.unwrap_or(Err(FetchError::DeadlineExceeded))).collect()
+}
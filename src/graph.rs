@@ -0,0 +1,267 @@
+// adjacency-list graph and the usual traversals
+// everything is iterative, so a 2e5 long path doesn't blow the stack like a recursive dfs would
+
+use std::collections::VecDeque;
+use std::io::Read;
+
+use crate::digits::PrimInt;
+use crate::heap::DaryHeap;
+use crate::scanner::{Scan, Scanner};
+
+/// Graph over vertices `0..n`, each edge carries a weight `W` (`()` for unweighted).
+/// An undirected edge is stored in both lists.
+///
+/// ```
+/// use rust_files::graph::Graph;
+/// use rust_files::scanner::Scanner;
+/// // n m, then m edges, 1-indexed like most Codeforces inputs
+/// let mut sc = Scanner::new("3 2\n1 2\n2 3\n".as_bytes());
+/// let g = Graph::read(&mut sc, false, true);
+/// assert_eq!(g.bfs(0), vec![Some(0), Some(1), Some(2)]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Graph<W = ()> {
+    adj: Vec<Vec<(usize, W)>>,
+    directed: bool,
+    edges: usize,
+}
+
+impl<W: Clone> Graph<W> {
+    pub fn new(n: usize, directed: bool) -> Self {
+        Graph { adj: vec![Vec::new(); n], directed, edges: 0 }
+    }
+
+    /// Edge `u -> v` (and `v -> u` if undirected).
+    pub fn add_edge(&mut self, u: usize, v: usize, w: W) {
+        if !self.directed {
+            self.adj[v].push((u, w.clone()));
+        }
+        self.adj[u].push((v, w));
+        self.edges += 1;
+    }
+
+    /// Number of vertices.
+    pub fn len(&self) -> usize {
+        self.adj.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adj.is_empty()
+    }
+
+    /// Number of edges added (an undirected edge counts once).
+    pub fn edge_count(&self) -> usize {
+        self.edges
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Outgoing `(vertex, weight)` pairs of `u`.
+    pub fn adj(&self, u: usize) -> &[(usize, W)] {
+        &self.adj[u]
+    }
+
+    /// Outgoing neighbours of `u`, without weights.
+    pub fn neighbors(&self, u: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adj[u].iter().map(|&(v, _)| v)
+    }
+
+    /// Same graph with every edge reversed.
+    pub fn reversed(&self) -> Self {
+        let mut r = Graph::new(self.len(), self.directed);
+        for u in 0..self.len() {
+            for (v, w) in &self.adj[u] {
+                r.adj[*v].push((u, w.clone()));
+            }
+        }
+        r.edges = self.edges;
+        r
+    }
+
+    /// Unweighted distances (edge count) from `src`, `None` if unreachable.
+    pub fn bfs(&self, src: usize) -> Vec<Option<usize>> {
+        let mut dist = vec![None; self.len()];
+        let mut q = VecDeque::new();
+        dist[src] = Some(0);
+        q.push_back(src);
+        while let Some(u) = q.pop_front() {
+            let d = dist[u].unwrap() + 1;
+            for v in self.neighbors(u) {
+                if dist[v].is_none() {
+                    dist[v] = Some(d);
+                    q.push_back(v);
+                }
+            }
+        }
+        dist
+    }
+
+    /// Vertices reachable from `src` in dfs preorder, visiting neighbours in insertion order
+    /// (the same order a recursive dfs gives).
+    pub fn dfs(&self, src: usize) -> Vec<usize> {
+        let mut seen = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![(src, 0)]; // (vertex, next edge to look at)
+        seen[src] = true;
+        order.push(src);
+        while let Some((u, i)) = stack.last_mut() {
+            match self.adj[*u].get(*i) {
+                Some(&(v, _)) => {
+                    *i += 1;
+                    if !seen[v] {
+                        seen[v] = true;
+                        order.push(v);
+                        stack.push((v, 0));
+                    }
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+        order
+    }
+
+    /// Kahn's algorithm on a directed graph, `None` if there is a cycle.
+    /// Among ready vertices the smallest index goes first, so the answer is deterministic.
+    pub fn topo_sort(&self) -> Option<Vec<usize>> {
+        let mut indeg = vec![0usize; self.len()];
+        for u in 0..self.len() {
+            for v in self.neighbors(u) {
+                indeg[v] += 1;
+            }
+        }
+        let mut ready: DaryHeap<usize, 2> = DaryHeap::new(self.len());
+        for (u, _) in indeg.iter().enumerate().filter(|&(_, &d)| d == 0) {
+            ready.push(u, u);
+        }
+        let mut order = Vec::with_capacity(self.len());
+        while let Some((u, _)) = ready.pop() {
+            order.push(u);
+            for v in self.neighbors(u) {
+                indeg[v] -= 1;
+                if indeg[v] == 0 {
+                    ready.push(v, v);
+                }
+            }
+        }
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Strongly connected components (iterative Tarjan).
+    /// Returns `(count, comp)` with component ids in topological order:
+    /// an edge `u -> v` always has `comp[u] <= comp[v]`.
+    pub fn scc(&self) -> (usize, Vec<usize>) {
+        const UNSEEN: usize = usize::MAX;
+        let n = self.len();
+        let (mut index, mut low) = (vec![UNSEEN; n], vec![0; n]);
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut comp = vec![0; n];
+        let (mut timer, mut count) = (0, 0);
+        for s in 0..n {
+            if index[s] != UNSEEN {
+                continue;
+            }
+            let mut call = vec![(s, 0)];
+            index[s] = timer;
+            low[s] = timer;
+            timer += 1;
+            stack.push(s);
+            on_stack[s] = true;
+            while let Some((u, i)) = call.last_mut() {
+                let u = *u;
+                if let Some(&(v, _)) = self.adj[u].get(*i) {
+                    *i += 1;
+                    if index[v] == UNSEEN {
+                        index[v] = timer;
+                        low[v] = timer;
+                        timer += 1;
+                        stack.push(v);
+                        on_stack[v] = true;
+                        call.push((v, 0));
+                    } else if on_stack[v] {
+                        low[u] = low[u].min(index[v]);
+                    }
+                    continue;
+                }
+                // u is finished: hand low up to the caller, close the component if u is its root
+                call.pop();
+                if let Some(&(p, _)) = call.last() {
+                    low[p] = low[p].min(low[u]);
+                }
+                if low[u] == index[u] {
+                    loop {
+                        let x = stack.pop().unwrap();
+                        on_stack[x] = false;
+                        comp[x] = count;
+                        if x == u {
+                            break;
+                        }
+                    }
+                    count += 1;
+                }
+            }
+        }
+        // tarjan closes sink components first, flip to topological order
+        for c in comp.iter_mut() {
+            *c = count - 1 - *c;
+        }
+        (count, comp)
+    }
+}
+
+impl<W: PrimInt> Graph<W> {
+    /// Shortest distances from `src` with non-negative weights, `None` if unreachable.
+    /// Panics if a distance overflows `W`.
+    pub fn dijkstra(&self, src: usize) -> Vec<Option<W>> {
+        let mut dist: Vec<Option<W>> = vec![None; self.len()];
+        let mut heap: DaryHeap<W, 4> = DaryHeap::new(self.len());
+        dist[src] = Some(W::ZERO);
+        heap.push(src, W::ZERO);
+        while let Some((u, d)) = heap.pop() {
+            for &(v, w) in &self.adj[u] {
+                let nd = d.checked_add(w).expect("dijkstra: distance overflow");
+                if dist[v].is_none_or(|old| nd < old) {
+                    dist[v] = Some(nd);
+                    heap.push_or_decrease(v, nd);
+                }
+            }
+        }
+        dist
+    }
+}
+
+// reads "n m" and then m edges, each `u v` followed by whatever `edge_weight` reads
+fn read_with<W: Clone, R: Read>(
+    sc: &mut Scanner<R>,
+    directed: bool,
+    one_based: bool,
+    mut edge_weight: impl FnMut(&mut Scanner<R>) -> W,
+) -> Graph<W> {
+    let (n, m): (usize, usize) = sc.next();
+    let mut g = Graph::new(n, directed);
+    let shift = usize::from(one_based);
+    for _ in 0..m {
+        let (u, v): (usize, usize) = sc.next();
+        let w = edge_weight(sc);
+        g.add_edge(u - shift, v - shift, w);
+    }
+    g
+}
+
+impl Graph<()> {
+    /// Reads `n m` and `m` lines of `u v`. Vertices are stored 0-indexed either way.
+    pub fn read<R: Read>(sc: &mut Scanner<R>, directed: bool, one_based: bool) -> Self {
+        read_with(sc, directed, one_based, |_| ())
+    }
+}
+
+impl<W: Scan + Clone> Graph<W> {
+    /// Reads `n m` and `m` lines of `u v w`.
+    pub fn read_weighted<R: Read>(sc: &mut Scanner<R>, directed: bool, one_based: bool) -> Self {
+        read_with(sc, directed, one_based, |sc| sc.next())
+    }
+}
@@ -0,0 +1,292 @@
+// rectangular 2d grid in one contiguous Vec (row-major), instead of a jagged Vec<Vec<char>>
+
+use std::fmt;
+use std::io::Read;
+use std::ops::{Index, IndexMut};
+
+use crate::scanner::{Scan, Scanner};
+
+/// Up, right, down, left as `(dr, dc)`.
+pub const DIRS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+/// The 4 directions plus the diagonals, clockwise from up.
+pub const DIRS8: [(isize, isize); 8] = [(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1)];
+
+/// `rows x cols` grid indexed by `(row, col)`.
+///
+/// ```
+/// use rust_files::grid::Grid;
+/// use rust_files::scanner::Scanner;
+/// let mut sc = Scanner::new("2 3\n#..\n.#.\n".as_bytes());
+/// let (n, _m): (usize, usize) = sc.next();
+/// let g = Grid::read_chars(&mut sc, n);
+/// assert_eq!(g[(1, 1)], '#');
+/// assert_eq!(g.neighbors4(0, 0).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+/// assert_eq!(format!("{:#}", g), "#..\n.#.\n");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds cell by cell, `f(row, col)`.
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let data = (0..rows * cols).map(|i| f(i / cols, i % cols)).collect();
+        Grid { rows, cols, data }
+    }
+
+    /// From rows of equal length. Panics on a jagged input.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let cols = rows.first().map_or(0, |r| r.len());
+        let n = rows.len();
+        let mut data = Vec::with_capacity(n * cols);
+        for (i, row) in rows.into_iter().enumerate() {
+            assert_eq!(row.len(), cols, "row {} has {} cells, expected {}", i, row.len(), cols);
+            data.extend(row);
+        }
+        Grid { rows: n, cols, data }
+    }
+
+    /// From a row-major `Vec` of `rows * cols` cells.
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<T>) -> Self {
+        assert_eq!(data.len(), rows * cols, "{} cells don't make a {}x{} grid", data.len(), rows, cols);
+        Grid { rows, cols, data }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Number of cells.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn in_bounds(&self, r: isize, c: isize) -> bool {
+        r >= 0 && c >= 0 && (r as usize) < self.rows && (c as usize) < self.cols
+    }
+
+    pub fn get(&self, r: usize, c: usize) -> Option<&T> {
+        (r < self.rows && c < self.cols).then(|| &self.data[r * self.cols + c])
+    }
+
+    pub fn get_mut(&mut self, r: usize, c: usize) -> Option<&mut T> {
+        (r < self.rows && c < self.cols).then(|| &mut self.data[r * self.cols + c])
+    }
+
+    /// `(r, c)` moved by `(dr, dc)`, `None` if that leaves the grid.
+    pub fn step(&self, (r, c): (usize, usize), (dr, dc): (isize, isize)) -> Option<(usize, usize)> {
+        let (nr, nc) = (r as isize + dr, c as isize + dc);
+        self.in_bounds(nr, nc).then_some((nr as usize, nc as usize))
+    }
+
+    /// In-bounds neighbours of `(r, c)` in the given directions, e.g. [`DIRS4`].
+    pub fn neighbors<'a>(
+        &'a self,
+        r: usize,
+        c: usize,
+        dirs: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        dirs.iter().filter_map(move |&d| self.step((r, c), d))
+    }
+
+    /// Up/right/down/left neighbours that exist.
+    pub fn neighbors4(&self, r: usize, c: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(r, c, &DIRS4)
+    }
+
+    /// All 8 surrounding cells that exist.
+    pub fn neighbors8(&self, r: usize, c: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(r, c, &DIRS8)
+    }
+
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.data[r * self.cols..(r + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, r: usize) -> &mut [T] {
+        &mut self.data[r * self.cols..(r + 1) * self.cols]
+    }
+
+    /// Cells of column `c`, top to bottom.
+    pub fn col(&self, c: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(c < self.cols, "column {} out of range", c);
+        self.data.iter().skip(c).step_by(self.cols)
+    }
+
+    /// Rows as slices, top to bottom.
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.rows).map(move |r| self.row(r))
+    }
+
+    /// All cells with their position, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.data.iter().enumerate().map(move |(i, x)| ((i / self.cols, i % self.cols), x))
+    }
+
+    /// First position (row by row) whose cell satisfies `pred`.
+    pub fn position(&self, pred: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.data.iter().position(pred).map(|i| (i / self.cols, i % self.cols))
+    }
+
+    /// Read-only view with rows and columns swapped, nothing is copied.
+    pub fn transposed(&self) -> Transposed<'_, T> {
+        Transposed { grid: self }
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { rows: self.rows, cols: self.cols, data: self.data.iter().map(f).collect() }
+    }
+
+    /// The cells row-major, e.g. to feed a prefix sum.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// `rows x cols` grid filled with `fill`.
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self {
+        Grid { rows, cols, data: vec![fill; rows * cols] }
+    }
+
+    /// Owned transpose, `cols x rows`.
+    pub fn transpose(&self) -> Grid<T> {
+        Grid::from_fn(self.cols, self.rows, |r, c| self[(c, r)].clone())
+    }
+}
+
+impl Grid<char> {
+    /// Reads `rows` tokens as rows of chars, e.g. a map of `#` and `.`. Panics if they differ in length.
+    pub fn read_chars<R: Read>(sc: &mut Scanner<R>, rows: usize) -> Self {
+        Grid::from_rows(sc.grid(rows))
+    }
+}
+
+impl Grid<u8> {
+    /// Like [`Grid::read_chars`], but keeps the raw bytes (cheaper for ASCII maps).
+    pub fn read_bytes<R: Read>(sc: &mut Scanner<R>, rows: usize) -> Self {
+        Grid::from_rows((0..rows).map(|_| sc.bytes()).collect())
+    }
+}
+
+impl<T: Scan> Grid<T> {
+    /// Reads `rows * cols` whitespace separated values, e.g. a matrix of numbers.
+    pub fn read<R: Read>(sc: &mut Scanner<R>, rows: usize, cols: usize) -> Self {
+        Grid::from_vec(rows, cols, sc.vec(rows * cols))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+    fn index(&self, (r, c): (usize, usize)) -> &T {
+        assert!(r < self.rows && c < self.cols, "({}, {}) outside a {}x{} grid", r, c, self.rows, self.cols);
+        &self.data[r * self.cols + c]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut T {
+        assert!(r < self.rows && c < self.cols, "({}, {}) outside a {}x{} grid", r, c, self.rows, self.cols);
+        &mut self.data[r * self.cols + c]
+    }
+}
+
+// `{}` pads every column to its widest cell and separates cells with a space (number matrices),
+// `{:#}` writes the cells back to back (char maps, the way they came in)
+fn write_cells<T: fmt::Display>(
+    f: &mut fmt::Formatter,
+    rows: usize,
+    cols: usize,
+    cell: impl Fn(usize, usize) -> T,
+) -> fmt::Result {
+    if f.alternate() {
+        for r in 0..rows {
+            for c in 0..cols {
+                write!(f, "{}", cell(r, c))?;
+            }
+            writeln!(f)?;
+        }
+        return Ok(());
+    }
+    let text: Vec<String> = (0..rows * cols).map(|i| cell(i / cols, i % cols).to_string()).collect();
+    let width: Vec<usize> = (0..cols)
+        .map(|c| (0..rows).map(|r| text[r * cols + c].chars().count()).max().unwrap_or(0))
+        .collect();
+    for r in 0..rows {
+        for c in 0..cols {
+            let sep = if c == 0 { "" } else { " " };
+            write!(f, "{}{:>w$}", sep, text[r * cols + c], w = width[c])?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_cells(f, self.rows, self.cols, |r, c| &self[(r, c)])
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter_rows()).finish()
+    }
+}
+
+/// See [`Grid::transposed`].
+pub struct Transposed<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl<'a, T> Transposed<'a, T> {
+    pub fn rows(&self) -> usize {
+        self.grid.cols()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.grid.rows()
+    }
+
+    pub fn get(&self, r: usize, c: usize) -> Option<&'a T> {
+        self.grid.get(c, r)
+    }
+
+    /// Row `r` of the view, i.e. column `r` of the grid.
+    pub fn row(&self, r: usize) -> impl Iterator<Item = &'a T> + 'a {
+        self.grid.col(r)
+    }
+
+    /// Column `c` of the view, i.e. row `c` of the grid.
+    pub fn col(&self, c: usize) -> &'a [T] {
+        self.grid.row(c)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Transposed<'_, T> {
+    type Output = T;
+    fn index(&self, (r, c): (usize, usize)) -> &T {
+        &self.grid[(c, r)]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Transposed<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_cells(f, self.rows(), self.cols(), |r, c| &self[(r, c)])
+    }
+}
@@ -0,0 +1,196 @@
+// implicit trees stored in arrays: index helpers, d-ary heap with decrease-key, heapify and checks
+
+/// 1-indexed complete binary tree, the cp layout: root 1, children `2i` and `2i + 1`.
+pub mod one_based {
+    pub fn parent(i: usize) -> usize {
+        i / 2
+    }
+
+    pub fn left(i: usize) -> usize {
+        2 * i
+    }
+
+    pub fn right(i: usize) -> usize {
+        2 * i + 1
+    }
+
+    /// Root is depth 0.
+    pub fn depth(i: usize) -> u32 {
+        debug_assert!(i >= 1);
+        usize::BITS - 1 - i.leading_zeros()
+    }
+
+    /// `a` is an ancestor of `b` (or `b` itself): `b` shifted right by the depth difference is `a`.
+    pub fn is_ancestor(a: usize, b: usize) -> bool {
+        let (da, db) = (depth(a), depth(b));
+        db >= da && b >> (db - da) == a
+    }
+
+    /// One index is the other times a power of two, i.e. both are on the same chain of left children
+    /// (`p, 2p, 4p, ...`). Heapify 1 (04a) checks this for a value that sits before its target
+    /// position, and [`is_ancestor`] for one that sits after it.
+    pub fn on_left_chain(a: usize, b: usize) -> bool {
+        let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+        lo >= 1 && hi.is_multiple_of(lo) && (hi / lo).is_power_of_two()
+    }
+}
+
+/// Parent of `i` in a 0-indexed d-ary tree (root 0). Don't call it on the root.
+pub fn parent(i: usize, d: usize) -> usize {
+    (i - 1) / d
+}
+
+/// Children of `i` in a 0-indexed d-ary tree, clipped to `len`.
+pub fn children(i: usize, d: usize, len: usize) -> std::ops::Range<usize> {
+    let first = (d * i + 1).min(len);
+    first..(first + d).min(len)
+}
+
+// moves a[i] down until no child is smaller
+fn sift_down<T: Ord>(a: &mut [T], mut i: usize, d: usize) {
+    loop {
+        let Some(c) = children(i, d, a.len()).min_by(|&x, &y| a[x].cmp(&a[y])) else { return };
+        if a[c] >= a[i] {
+            return;
+        }
+        a.swap(i, c);
+        i = c;
+    }
+}
+
+/// Turns `a` into a d-ary min-heap in place, O(n). For a max-heap use `std::cmp::Reverse` items.
+pub fn heapify<T: Ord>(a: &mut [T], d: usize) {
+    assert!(d >= 2, "heap arity must be at least 2");
+    if a.len() < 2 {
+        return;
+    }
+    for i in (0..=parent(a.len() - 1, d)).rev() {
+        sift_down(a, i, d);
+    }
+}
+
+/// Every node is <= its children (0-indexed d-ary min-heap).
+pub fn is_heap<T: Ord>(a: &[T], d: usize) -> bool {
+    (1..a.len()).all(|i| a[parent(i, d)] <= a[i])
+}
+
+/// Min-heap of ids `0..n` with priorities, `D` children per node. Each id is in the heap at most
+/// once, so priorities can be lowered in place (`decrease_key`) instead of pushing duplicates.
+///
+/// ```
+/// use rust_files::heap::DaryHeap;
+/// let mut h: DaryHeap<u64, 4> = DaryHeap::new(3);
+/// h.push(0, 10);
+/// h.push(1, 5);
+/// h.push(2, 7);
+/// h.decrease_key(0, 1);
+/// assert_eq!(h.pop(), Some((0, 1)));
+/// assert_eq!(h.pop(), Some((1, 5)));
+/// ```
+pub struct DaryHeap<P, const D: usize> {
+    heap: Vec<(P, usize)>, // (priority, id)
+    pos: Vec<Option<usize>>, // pos[id] = index in `heap`
+}
+
+impl<P: Ord + Copy, const D: usize> DaryHeap<P, D> {
+    /// Empty heap for ids `0..n`.
+    pub fn new(n: usize) -> Self {
+        assert!(D >= 2, "heap arity must be at least 2");
+        DaryHeap { heap: Vec::new(), pos: vec![None; n] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn contains(&self, id: usize) -> bool {
+        self.pos[id].is_some()
+    }
+
+    /// Current priority of `id`, if it is in the heap.
+    pub fn priority(&self, id: usize) -> Option<P> {
+        self.pos[id].map(|i| self.heap[i].0)
+    }
+
+    pub fn peek(&self) -> Option<(usize, P)> {
+        self.heap.first().map(|&(p, id)| (id, p))
+    }
+
+    /// Adds `id`. Panics if it is already in the heap, use [`DaryHeap::push_or_decrease`] then.
+    pub fn push(&mut self, id: usize, prio: P) {
+        assert!(self.pos[id].is_none(), "id {} is already in the heap", id);
+        self.heap.push((prio, id));
+        self.pos[id] = Some(self.heap.len() - 1);
+        self.sift_up(self.heap.len() - 1);
+    }
+
+    /// Lowers the priority of `id`. Panics if `id` is missing or `prio` is larger than the current one.
+    pub fn decrease_key(&mut self, id: usize, prio: P) {
+        let i = self.pos[id].expect("decrease_key on an id that is not in the heap");
+        assert!(prio <= self.heap[i].0, "decrease_key can't increase a priority");
+        self.heap[i].0 = prio;
+        self.sift_up(i);
+    }
+
+    /// Dijkstra style relax: pushes `id` or lowers its priority, returns whether anything changed.
+    pub fn push_or_decrease(&mut self, id: usize, prio: P) -> bool {
+        match self.priority(id) {
+            None => {
+                self.push(id, prio);
+                true
+            }
+            Some(cur) if prio < cur => {
+                self.decrease_key(id, prio);
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
+    /// Removes and returns the `(id, priority)` with the smallest priority.
+    pub fn pop(&mut self) -> Option<(usize, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        let (prio, id) = self.heap.pop().unwrap();
+        self.pos[id] = None;
+        self.sift_down(0);
+        Some((id, prio))
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.pos[self.heap[i].1] = Some(i);
+        self.pos[self.heap[j].1] = Some(j);
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let p = parent(i, D);
+            if self.heap[p] <= self.heap[i] {
+                break;
+            }
+            self.swap(i, p);
+            i = p;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let Some(c) = children(i, D, self.heap.len()).min_by(|&x, &y| self.heap[x].cmp(&self.heap[y])) else {
+                return;
+            };
+            if self.heap[c] >= self.heap[i] {
+                return;
+            }
+            self.swap(i, c);
+            i = c;
+        }
+    }
+}
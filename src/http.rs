@@ -0,0 +1,209 @@
+// async http on one shared reqwest::Client: timeouts, retries with exponential backoff,
+// non-2xx answers as typed errors and json straight into structs
+// (07f started as a bare `reqwest::get(url).await?.text()`)
+
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+
+/// Why a request failed, with the url it was for.
+#[derive(Debug)]
+pub enum HttpError {
+    /// The server answered, but not with 2xx. 5xx only shows up once the retries are used up.
+    Status { url: String, status: StatusCode, body: String },
+    /// No answer within the configured timeout.
+    Timeout { url: String },
+    /// Could not connect, even after retrying.
+    Connect { url: String, source: reqwest::Error },
+    /// 2xx, but the body is not the expected json.
+    Decode { url: String, source: serde_json::Error },
+    /// Anything else reqwest reports: a bad url, a body cut short, ...
+    Other { url: String, source: reqwest::Error },
+}
+
+impl HttpError {
+    fn from_reqwest(url: &str, e: reqwest::Error) -> Self {
+        let url = url.to_string();
+        if e.is_timeout() {
+            HttpError::Timeout { url }
+        } else if e.is_connect() {
+            HttpError::Connect { url, source: e }
+        } else {
+            HttpError::Other { url, source: e }
+        }
+    }
+
+    /// The status code for `Status` errors.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            HttpError::Status { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// 5xx and connection failures are worth another try, a 404 or bad json will not change.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            HttpError::Status { status, .. } => status.is_server_error(),
+            HttpError::Connect { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HttpError::Status { url, status, .. } => write!(f, "GET {}: server answered {}", url, status),
+            HttpError::Timeout { url } => write!(f, "GET {}: timed out", url),
+            HttpError::Connect { url, source } => write!(f, "GET {}: cannot connect: {}", url, source),
+            HttpError::Decode { url, source } => write!(f, "GET {}: unexpected json: {}", url, source),
+            HttpError::Other { url, source } => write!(f, "GET {}: {}", url, source),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HttpError::Connect { source, .. } | HttpError::Other { source, .. } => Some(source),
+            HttpError::Decode { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A `reqwest::Client` plus a retry policy. Cloning is cheap and shares the connection pool.
+///
+/// ```no_run
+/// # async fn demo() -> Result<(), rust_files::http::HttpError> {
+/// use std::time::Duration;
+/// use rust_files::http::HttpClient;
+/// let client = HttpClient::builder().timeout(Duration::from_secs(2)).retries(2).build();
+/// let body = client.get_text("http://127.0.0.1:8080/users/1").await?;
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    retries: u32,
+    backoff: Duration,
+    max_backoff: Duration,
+}
+
+/// Settings for [`HttpClient`], see [`HttpClient::builder`].
+#[derive(Clone, Debug)]
+pub struct HttpClientBuilder {
+    timeout: Duration,
+    connect_timeout: Duration,
+    retries: u32,
+    backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Default for HttpClientBuilder {
+    fn default() -> Self {
+        HttpClientBuilder {
+            timeout: Duration::from_secs(10),
+            connect_timeout: Duration::from_secs(5),
+            retries: 3,
+            backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl HttpClientBuilder {
+    /// Whole request, connect to last body byte. Default 10s.
+    pub fn timeout(mut self, d: Duration) -> Self {
+        self.timeout = d;
+        self
+    }
+
+    /// Just the tcp connect. Default 5s.
+    pub fn connect_timeout(mut self, d: Duration) -> Self {
+        self.connect_timeout = d;
+        self
+    }
+
+    /// Extra attempts after the first for retryable failures. Default 3.
+    pub fn retries(mut self, n: u32) -> Self {
+        self.retries = n;
+        self
+    }
+
+    /// Wait before the first retry, doubled for each next one and capped at `max`. Default 200ms, 5s.
+    pub fn backoff(mut self, first: Duration, max: Duration) -> Self {
+        self.backoff = first;
+        self.max_backoff = max;
+        self
+    }
+
+    pub fn build(self) -> HttpClient {
+        let client = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .connect_timeout(self.connect_timeout)
+            .build()
+            .expect("cannot create the http client (tls backend failed to load)");
+        HttpClient { client, retries: self.retries, backoff: self.backoff, max_backoff: self.max_backoff }
+    }
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        HttpClientBuilder::default().build()
+    }
+}
+
+impl HttpClient {
+    pub fn builder() -> HttpClientBuilder {
+        HttpClientBuilder::default()
+    }
+
+    /// One client with the default settings for the whole program, so every caller shares its pool.
+    pub fn shared() -> &'static HttpClient {
+        static SHARED: OnceLock<HttpClient> = OnceLock::new();
+        SHARED.get_or_init(HttpClient::default)
+    }
+
+    /// How long to wait before retry number `attempt` (0 = the first retry).
+    pub fn backoff_delay(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+        self.backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+
+    /// GET with retries; a 2xx response comes back with its body unread.
+    pub async fn get(&self, url: &str) -> Result<reqwest::Response, HttpError> {
+        let mut attempt = 0;
+        loop {
+            let err = match self.client.get(url).send().await {
+                Ok(resp) if resp.status().is_success() => return Ok(resp),
+                Ok(resp) => {
+                    let status = resp.status();
+                    let body = resp.text().await.unwrap_or_default();
+                    HttpError::Status { url: url.to_string(), status, body }
+                }
+                Err(e) => HttpError::from_reqwest(url, e),
+            };
+            if attempt >= self.retries || !err.is_retryable() {
+                return Err(err);
+            }
+            tokio::time::sleep(self.backoff_delay(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    /// GET and read the body as text.
+    pub async fn get_text(&self, url: &str) -> Result<String, HttpError> {
+        self.get(url).await?.text().await.map_err(|e| HttpError::from_reqwest(url, e))
+    }
+
+    /// GET and deserialize the json body into `T`.
+    pub async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, HttpError> {
+        let body = self.get_text(url).await?;
+        serde_json::from_str(&body).map_err(|e| HttpError::Decode { url: url.to_string(), source: e })
+    }
+}
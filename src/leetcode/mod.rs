@@ -0,0 +1,76 @@
+// leetcode solutions with their sample cases, so the expected outputs are checked instead of read by eye
+// run them with: cargo run --bin leetcode -- list | all | <problem id>...
+
+use std::fmt::Debug;
+
+// the Solution impls are moved from src/bin exactly as they were written there, lints and all
+#[allow(clippy::all)]
+pub mod p0003_longest_substring;
+#[allow(clippy::all)]
+pub mod p0007_reverse_integer;
+#[allow(clippy::all)]
+pub mod p0007_reverse_integer_digits;
+#[allow(clippy::all)]
+pub mod p0008_string_to_integer;
+
+/// Every registered problem, ordered by id. Add new ones here.
+pub fn problems() -> Vec<Problem> {
+    let mut v = vec![
+        p0003_longest_substring::problem(),
+        p0003_longest_substring::problem_linear(),
+        p0007_reverse_integer::problem(),
+        p0007_reverse_integer_digits::problem(),
+        p0007_reverse_integer::problem_checked(),
+        p0008_string_to_integer::problem(),
+        p0008_string_to_integer::problem_saturating(),
+    ];
+    v.sort_by_key(|p| p.id);
+    v
+}
+
+/// One solution function plus its sample cases.
+pub struct Problem {
+    pub id: u32,
+    pub name: &'static str,
+    runner: Box<dyn Fn() -> Vec<CaseResult>>,
+}
+
+/// Outcome of one sample, values already formatted with `{:?}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseResult {
+    pub input: String,
+    pub expected: String,
+    pub actual: String,
+    pub passed: bool,
+}
+
+impl Problem {
+    /// Registers `f` with `(input, expected)` samples. For several arguments use a tuple input
+    /// and a closure, e.g. `|(nums, k)| Solution::f(nums, k)`.
+    pub fn new<I, O, F>(id: u32, name: &'static str, f: F, cases: Vec<(I, O)>) -> Self
+    where
+        I: Clone + Debug + 'static,
+        O: PartialEq + Debug + 'static,
+        F: Fn(I) -> O + 'static,
+    {
+        let runner = move || {
+            cases
+                .iter()
+                .map(|(input, expected)| {
+                    let actual = f(input.clone());
+                    CaseResult {
+                        input: format!("{:?}", input),
+                        expected: format!("{:?}", expected),
+                        actual: format!("{:?}", actual),
+                        passed: actual == *expected,
+                    }
+                })
+                .collect()
+        };
+        Problem { id, name, runner: Box::new(runner) }
+    }
+
+    pub fn run(&self) -> Vec<CaseResult> {
+        (self.runner)()
+    }
+}
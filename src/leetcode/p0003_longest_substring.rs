@@ -0,0 +1,55 @@
+// leetcode: 3. Longest Substring Without Repeating Characters
+// https://leetcode.com/problems/longest-substring-without-repeating-characters/
+// first attempt from test3.rs, correct but tle: chars().nth(i) is O(i) and the window restarts on every repeat
+// the solution is kept as written there
+// #![allow(unused)]
+
+use super::Problem;
+use crate::window::longest_unique_substring;
+
+pub struct Solution;
+
+impl Solution {
+    pub fn length_of_longest_substring(s: String) -> i32 {
+        use std::collections::HashMap;
+        let len:usize = s.len();
+        if len == 0 {return 0;}
+        let mut mp: HashMap<char, usize> = HashMap::new();   
+        let mut res:usize = 1;
+        let mut l:usize = 0;
+        let mut r:usize = 0;
+        while r<len{
+            for i in l..len {
+                let c:char = s.chars().nth(i).unwrap();
+                if mp.contains_key(&c){                    
+                    l = mp.get(&c).copied().unwrap()+1;                   
+                    mp.clear();
+                    break;
+                }else{
+                    mp.insert(c, i);                    
+                    r= i+1;
+                    res = std::cmp::max(res, r - l); // 
+                }            
+            }
+        }
+        return res as i32;
+    }
+}
+
+fn cases() -> Vec<(String, i32)> {
+    let cases = [("abcabcbb", 3), ("bbbbb", 1), ("pwwkew", 3), ("", 0), ("dvdf", 3), ("abba", 2)];
+    cases.iter().map(|&(s, n)| (s.to_string(), n)).collect()
+}
+
+pub fn problem() -> Problem {
+    Problem::new(3, "Longest Substring Without Repeating Characters", Solution::length_of_longest_substring, cases())
+}
+
+pub fn problem_linear() -> Problem {
+    Problem::new(
+        3,
+        "Longest Substring Without Repeating Characters (window)",
+        |s: String| longest_unique_substring(&s) as i32,
+        cases(),
+    )
+}
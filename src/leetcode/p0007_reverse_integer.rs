@@ -0,0 +1,40 @@
+// leetcode: 7. Reverse Integer // https://leetcode.com/problems/reverse-integer/
+// from cp2.rs (i64 widening), kept as written there; the second attempt from cp3.rs is in
+// p0007_reverse_integer_digits.rs
+
+#![allow(unused)]
+
+use super::Problem;
+use crate::digits::reverse_digits;
+
+pub struct Solution;
+
+impl Solution {
+    pub fn reverse(x: i32) -> i32 {
+        let mut num = x as i64;
+        let mut result: i64 = 0;
+        
+        while num != 0 {
+            result = result * 10 + num % 10;
+            num /= 10;
+        }
+        
+        if result > i32::MAX as i64 || result < i32::MIN as i64 {
+            0
+        } else {
+            result as i32
+        }
+    }
+}
+
+pub(super) fn cases() -> Vec<(i32, i32)> {
+    vec![(123, 321), (-123, -321), (120, 21), (0, 0), (1534236469, 0), (i32::MIN, 0), (-2147483412, -2143847412)]
+}
+
+pub fn problem() -> Problem {
+    Problem::new(7, "Reverse Integer", Solution::reverse, cases())
+}
+
+pub fn problem_checked() -> Problem {
+    Problem::new(7, "Reverse Integer (digits::reverse_digits)", |x: i32| reverse_digits(x).unwrap_or(0), cases())
+}
@@ -0,0 +1,49 @@
+// leetcode: 8. String to Integer (atoi) // https://leetcode.com/problems/string-to-integer-atoi/description/
+// accepted, april 11, 2026, beats 100% (from cp1.rs, kept as written there)
+
+#![allow(unused)]
+
+use super::Problem;
+use crate::digits::saturating_parse;
+
+pub struct Solution;
+
+impl Solution {
+    pub fn my_atoi(s: String) -> i32 {
+        let mut started:bool = false;
+        let mut neg:bool = false;
+        let mut v:Vec<char> = Vec::new();
+        let mut res:i64 = 0;
+        let mn:i64 = i32::MIN as i64; // -2^31 = -2147483648
+        let mx:i64 = i32::MAX as i64; // 2^31 - 1 = 2147483647
+        for (_, c) in s.chars().enumerate() {
+            if c == ' ' && !started {continue;}
+            if !started && c == '-' {neg = true; started = true; continue;}
+            if !started && c == '+' {neg = false; started = true; continue;}
+            if !c.is_ascii_digit() {break;} // '0' to '9'
+            started = true;
+            v.push(c);
+        }
+        let len = v.len();
+        for i in 0..len {
+            res = res * 10 + (v[i] as i64 - '0' as i64); // '0' == 48
+            if neg && res > mx+1 {return i32::MIN;}
+            if !neg && res > mx {return i32::MAX;}
+        }
+        if neg {res = -res;}
+        return res as i32;
+    }
+}
+
+fn cases() -> Vec<(String, i32)> {
+    let cases = [("42", 42), ("  -042", -42), ("1337c0d3", 1337), ("0-1", 0), ("words and 987", 0), ("-91283472332", i32::MIN)];
+    cases.iter().map(|&(s, n)| (s.to_string(), n)).collect()
+}
+
+pub fn problem() -> Problem {
+    Problem::new(8, "String to Integer (atoi)", Solution::my_atoi, cases())
+}
+
+pub fn problem_saturating() -> Problem {
+    Problem::new(8, "String to Integer (atoi) (digits::saturating_parse)", |s: String| saturating_parse::<i32>(&s), cases())
+}
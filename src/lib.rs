@@ -0,0 +1,27 @@
+// shared helpers for the binaries in src/bin (cp input/output, data structures, async/http)
+// use from a binary as: use rust_files::scanner::Scanner;
+
+pub mod actor;
+pub mod bundle;
+pub mod cancel;
+pub mod cases;
+pub mod digits;
+pub mod ds;
+pub mod fanout;
+pub mod graph;
+pub mod grid;
+pub mod heap;
+pub mod http;
+pub mod leetcode;
+pub mod line_server;
+pub mod number_theory;
+pub mod ops;
+pub mod output;
+pub mod pipeline;
+pub mod prefix;
+pub mod scanner;
+pub mod scheduler;
+pub mod stress;
+pub mod testcase;
+pub mod users;
+pub mod window;
@@ -0,0 +1,149 @@
+// newline-delimited command server, the grown-up version of the echo server sketched in 07e:
+//   ECHO <text>  -> <text>
+//   TIME         -> server time, rfc 3339
+//   COUNT        -> commands served so far, all clients together (this one included)
+//   QUIT         -> BYE, then the server closes the connection
+// anything else gets `ERR ...`; commands are case-insensitive, empty lines are ignored
+
+use std::future::Future;
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{Semaphore, watch};
+use tokio::task::JoinSet;
+
+// pause after a failed accept()
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Limits of a [`serve`] run.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Clients connected at once; the next one gets `ERR server busy` and is closed.
+    pub max_connections: usize,
+    /// A client that sends nothing for this long gets `BYE idle timeout` and is closed.
+    pub idle_timeout: Duration,
+    /// Longest accepted line in bytes, newline included.
+    pub max_line: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { max_connections: 64, idle_timeout: Duration::from_secs(60), max_line: 4096 }
+    }
+}
+
+enum Reply {
+    Line(String),
+    Quit,
+}
+
+fn respond(line: &str, served: &AtomicU64) -> Reply {
+    let n = served.fetch_add(1, Ordering::SeqCst) + 1;
+    let (cmd, arg) = line.split_once(' ').unwrap_or((line, ""));
+    match cmd.to_ascii_uppercase().as_str() {
+        "ECHO" => Reply::Line(arg.to_string()),
+        "TIME" => Reply::Line(chrono::Local::now().to_rfc3339()),
+        "COUNT" => Reply::Line(n.to_string()),
+        "QUIT" => Reply::Quit,
+        _ => Reply::Line(format!("ERR unknown command: {}", cmd)),
+    }
+}
+
+async fn handle(
+    sock: TcpStream,
+    config: &Config,
+    served: &AtomicU64,
+    mut stop: watch::Receiver<bool>,
+) -> io::Result<()> {
+    let (r, mut w) = sock.into_split();
+    let mut reader = BufReader::new(r);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let mut limited = (&mut reader).take(config.max_line as u64);
+        // a command that was read is always answered, shutdown only cuts in while waiting for the next one
+        let read = tokio::select! {
+            _ = stop.wait_for(|&stopping| stopping) => None,
+            read = tokio::time::timeout(config.idle_timeout, limited.read_line(&mut line)) => Some(read),
+        };
+        let Some(read) = read else {
+            return w.write_all(b"BYE server shutting down\n").await;
+        };
+        match read {
+            Err(_) => return w.write_all(b"BYE idle timeout\n").await,
+            Ok(Err(e)) if e.kind() == io::ErrorKind::InvalidData => {
+                return w.write_all(b"ERR not utf-8\n").await;
+            }
+            Ok(Err(e)) => return Err(e),
+            Ok(Ok(0)) => return Ok(()), // client hung up
+            Ok(Ok(_)) => {}
+        }
+        if !line.ends_with('\n') && line.len() >= config.max_line {
+            return w.write_all(b"ERR line too long\n").await;
+        }
+        let cmd = line.trim_end_matches(['\r', '\n']);
+        if cmd.trim().is_empty() {
+            continue;
+        }
+        match respond(cmd, served) {
+            Reply::Line(mut s) => {
+                s.push('\n');
+                w.write_all(s.as_bytes()).await?;
+            }
+            Reply::Quit => return w.write_all(b"BYE\n").await,
+        }
+    }
+}
+
+/// Serves clients from `listener` until `shutdown` completes (e.g. `tokio::signal::ctrl_c()`),
+/// then stops accepting, tells every open connection `BYE server shutting down` once its current
+/// command is answered, and returns when all of them are closed.
+pub async fn serve(listener: TcpListener, config: Config, shutdown: impl Future) {
+    let config = Arc::new(config);
+    let served = Arc::new(AtomicU64::new(0));
+    let slots = Arc::new(Semaphore::new(config.max_connections));
+    let (stop_tx, stop_rx) = watch::channel(false);
+    let mut conns = JoinSet::new();
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            accepted = listener.accept() => {
+                let (mut sock, addr) = match accepted {
+                    Ok(a) => a,
+                    Err(e) => {
+                        // e.g. out of file descriptors (EMFILE): the listener is still fine, but retrying
+                        // at once would spin and flood stderr, so give connections time to close first
+                        eprintln!("line_server: accept failed: {}", e);
+                        tokio::select! {
+                            _ = &mut shutdown => break,
+                            _ = tokio::time::sleep(ACCEPT_BACKOFF) => continue,
+                        }
+                    }
+                };
+                let Ok(slot) = slots.clone().try_acquire_owned() else {
+                    conns.spawn(async move {
+                        let _ = sock.write_all(b"ERR server busy\n").await;
+                    });
+                    continue;
+                };
+                let (config, served, stop) = (config.clone(), served.clone(), stop_rx.clone());
+                conns.spawn(async move {
+                    if let Err(e) = handle(sock, &config, &served, stop).await {
+                        eprintln!("line_server: {}: {}", addr, e);
+                    }
+                    drop(slot);
+                });
+            }
+            // reap finished connections so the set doesn't grow forever
+            Some(_) = conns.join_next(), if !conns.is_empty() => {}
+        }
+    }
+    drop(listener);
+    let _ = stop_tx.send(true);
+    while conns.join_next().await.is_some() {}
+}
@@ -0,0 +1,308 @@
+// number theory for cp: gcd/lcm, extended euclid, modular arithmetic, sieve, factorisation
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+/// Greatest common divisor for any integer type, e.g. `gcd(12i64, 18)` or `gcd(12u32, 18)`.
+/// For negative inputs the sign of the result follows the `%` chain, same as the old 04c/04d version.
+pub fn gcd<T>(mut a: T, mut b: T) -> T
+where
+    T: Copy + PartialEq + Default + Rem<Output = T>,
+{
+    let zero = T::default();
+    while b != zero {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+/// Least common multiple, `lcm(0, x) == 0`. Divides before multiplying to delay overflow.
+pub fn lcm<T>(a: T, b: T) -> T
+where
+    T: Copy + PartialEq + Default + Rem<Output = T> + Div<Output = T> + Mul<Output = T>,
+{
+    if a == T::default() || b == T::default() {
+        return T::default();
+    }
+    a / gcd(a, b) * b
+}
+
+/// Extended Euclid: returns `(g, x, y)` with `a*x + b*y == g == gcd(a, b)`, `g >= 0`.
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    // iterative, keeps (old_r, r) and the matching coefficients
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1i64, 0i64);
+    let (mut old_y, mut y) = (0i64, 1i64);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Inverse of `a` modulo `m` in `[0, m)`, `None` if `gcd(a, m) != 1`. `m` need not be prime.
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(m))
+}
+
+/// `base^exp mod m` by binary exponentiation, u128 products so any u64 modulus works.
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    if m == 1 {
+        return 0;
+    }
+    let m = m as u128;
+    let mut b = base as u128 % m;
+    let mut res = 1u128;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = res * b % m;
+        }
+        b = b * b % m;
+        exp >>= 1;
+    }
+    res as u64
+}
+
+/// Integer modulo the compile-time constant `M`, with the usual operators.
+///
+/// Usage:
+/// ```
+/// use rust_files::number_theory::ModInt;
+/// type Mint = ModInt<1_000_000_007>;
+/// let a = Mint::new(500_000_004);
+/// assert_eq!(a * 2, Mint::new(1));
+/// assert_eq!(Mint::new(1) / 2, a);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ModInt<const M: u64>(u64);
+
+pub type ModInt998 = ModInt<998_244_353>;
+pub type ModInt1e9 = ModInt<1_000_000_007>;
+
+impl<const M: u64> ModInt<M> {
+    pub fn new(x: u64) -> Self {
+        ModInt(x % M)
+    }
+
+    /// The value in `[0, M)`.
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(self, exp: u64) -> Self {
+        ModInt(mod_pow(self.0, exp, M))
+    }
+
+    /// Multiplicative inverse, panics if it doesn't exist (value shares a factor with `M`).
+    pub fn inv(self) -> Self {
+        // ext_gcd in i128: M may not fit in an i64
+        let (mut old_r, mut r) = (self.0 as i128, M as i128);
+        let (mut old_x, mut x) = (1i128, 0i128);
+        while r != 0 {
+            let q = old_r / r;
+            (old_r, r) = (r, old_r - q * r);
+            (old_x, x) = (x, old_x - q * x);
+        }
+        assert!(old_r == 1, "ModInt: value has no inverse modulo M");
+        ModInt(old_x.rem_euclid(M as i128) as u64)
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(x: u64) -> Self {
+        ModInt::new(x)
+    }
+}
+
+impl<const M: u64> From<i64> for ModInt<M> {
+    fn from(x: i64) -> Self {
+        ModInt((x as i128).rem_euclid(M as i128) as u64)
+    }
+}
+
+impl<const M: u64> From<usize> for ModInt<M> {
+    fn from(x: usize) -> Self {
+        ModInt::new(x as u64)
+    }
+}
+
+impl<const M: u64> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const M: u64> fmt::Debug for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0) // plain number, easier to read in assert output
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        // both < M, but the sum can still pass u64::MAX when M > 2^63
+        let (s, carry) = self.0.overflowing_add(rhs.0);
+        ModInt(if carry || s >= M { s.wrapping_sub(M) } else { s })
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        ModInt(if self.0 >= rhs.0 { self.0 - rhs.0 } else { M - (rhs.0 - self.0) })
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        ModInt((self.0 as u128 * rhs.0 as u128 % M as u128) as u64)
+    }
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)] // division is multiplication by the inverse
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        ModInt(if self.0 == 0 { 0 } else { M - self.0 })
+    }
+}
+
+// `a + 1`, `a * 2` etc. with plain u64 on the right, and the `op=` forms
+macro_rules! impl_modint_ops {
+    ($($tr:ident $f:ident $atr:ident $af:ident),*) => {$(
+        impl<const M: u64> $tr<u64> for ModInt<M> {
+            type Output = Self;
+            fn $f(self, rhs: u64) -> Self {
+                $tr::$f(self, ModInt::new(rhs))
+            }
+        }
+        impl<const M: u64> $atr for ModInt<M> {
+            fn $af(&mut self, rhs: Self) {
+                *self = $tr::$f(*self, rhs);
+            }
+        }
+        impl<const M: u64> $atr<u64> for ModInt<M> {
+            fn $af(&mut self, rhs: u64) {
+                *self = $tr::$f(*self, ModInt::new(rhs));
+            }
+        }
+    )*};
+}
+
+impl_modint_ops!(Add add AddAssign add_assign, Sub sub SubAssign sub_assign, Mul mul MulAssign mul_assign, Div div DivAssign div_assign);
+
+impl<const M: u64> std::iter::Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(ModInt(0), |a, b| a + b)
+    }
+}
+
+impl<const M: u64> std::iter::Product for ModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(ModInt::new(1), |a, b| a * b)
+    }
+}
+
+/// Linear sieve up to `n`: primes plus the smallest prime factor of every number, O(n).
+pub struct Sieve {
+    spf: Vec<u32>, // spf[x] = smallest prime factor of x, 0 for x < 2
+    primes: Vec<u32>,
+}
+
+impl Sieve {
+    pub fn new(n: usize) -> Self {
+        let mut spf = vec![0u32; n + 1];
+        let mut primes = Vec::new();
+        for i in 2..=n {
+            if spf[i] == 0 {
+                spf[i] = i as u32;
+                primes.push(i as u32);
+            }
+            // every composite is crossed once, by its smallest prime factor
+            for &p in &primes {
+                let j = i * p as usize;
+                if p > spf[i] || j > n {
+                    break;
+                }
+                spf[j] = p;
+            }
+        }
+        Sieve { spf, primes }
+    }
+
+    /// Largest number covered.
+    pub fn limit(&self) -> usize {
+        self.spf.len() - 1
+    }
+
+    pub fn primes(&self) -> &[u32] {
+        &self.primes
+    }
+
+    pub fn is_prime(&self, x: usize) -> bool {
+        x >= 2 && self.spf[x] as usize == x
+    }
+
+    /// Smallest prime factor, `None` for 0 and 1.
+    pub fn spf(&self, x: usize) -> Option<u32> {
+        if x < 2 { None } else { Some(self.spf[x]) }
+    }
+
+    /// `(prime, exponent)` pairs in increasing prime order, O(log x). `x` must be <= `limit()`.
+    pub fn factorize(&self, mut x: usize) -> Vec<(u64, u32)> {
+        let mut res: Vec<(u64, u32)> = Vec::new();
+        while x >= 2 {
+            let p = self.spf[x] as usize;
+            let mut e = 0;
+            while x.is_multiple_of(p) {
+                x /= p;
+                e += 1;
+            }
+            res.push((p as u64, e));
+        }
+        res
+    }
+}
+
+/// Trial-division factorisation for numbers too big for a sieve, O(sqrt n).
+pub fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    let mut res = Vec::new();
+    let mut p = 2u64;
+    while p <= n / p {
+        // not p * p <= n: for a prime n near u64::MAX, p passes 2^32 and p * p overflows
+        if n.is_multiple_of(p) {
+            let mut e = 0;
+            while n.is_multiple_of(p) {
+                n /= p;
+                e += 1;
+            }
+            res.push((p, e));
+        }
+        p += if p == 2 { 1 } else { 2 };
+    }
+    if n > 1 {
+        res.push((n, 1));
+    }
+    res
+}
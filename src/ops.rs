@@ -0,0 +1,117 @@
+// associative operations for the range structures (prefix.rs, ds.rs)
+// an operation is a unit struct, so one `Sum` works for every number type: PrefixAgg<u64, Sum>
+
+use std::ops::{Add, BitAnd, BitOr, BitXor, Rem, Sub};
+
+use crate::digits::PrimInt;
+use crate::number_theory::gcd;
+
+/// Associative operation with an identity element: `op(id(), x) == x`.
+pub trait Monoid<T> {
+    fn id() -> T;
+    fn op(a: &T, b: &T) -> T;
+}
+
+/// Monoid that can be undone: `inv_op(&op(a, b), b) == a`. Ranges then come from two prefixes.
+pub trait Group<T>: Monoid<T> {
+    fn inv_op(a: &T, b: &T) -> T;
+}
+
+/// Marker for `op(x, x) == x` (min, max, gcd, and, or). Overlapping ranges are then fine,
+/// which is what a sparse table needs.
+pub trait Idempotent<T>: Monoid<T> {}
+
+pub struct Sum;
+pub struct Min;
+pub struct Max;
+pub struct Xor;
+pub struct Gcd;
+pub struct And;
+pub struct Or;
+
+impl<T: Copy + Default + Add<Output = T>> Monoid<T> for Sum {
+    fn id() -> T {
+        T::default()
+    }
+    fn op(a: &T, b: &T) -> T {
+        *a + *b
+    }
+}
+
+impl<T: Copy + Default + Add<Output = T> + Sub<Output = T>> Group<T> for Sum {
+    fn inv_op(a: &T, b: &T) -> T {
+        *a - *b
+    }
+}
+
+impl<T: PrimInt> Monoid<T> for Min {
+    fn id() -> T {
+        T::MAX
+    }
+    fn op(a: &T, b: &T) -> T {
+        *a.min(b)
+    }
+}
+
+impl<T: PrimInt> Idempotent<T> for Min {}
+
+impl<T: PrimInt> Monoid<T> for Max {
+    fn id() -> T {
+        T::MIN
+    }
+    fn op(a: &T, b: &T) -> T {
+        *a.max(b)
+    }
+}
+
+impl<T: PrimInt> Idempotent<T> for Max {}
+
+impl<T: Copy + Default + BitXor<Output = T>> Monoid<T> for Xor {
+    fn id() -> T {
+        T::default()
+    }
+    fn op(a: &T, b: &T) -> T {
+        *a ^ *b
+    }
+}
+
+impl<T: Copy + Default + BitXor<Output = T>> Group<T> for Xor {
+    fn inv_op(a: &T, b: &T) -> T {
+        *a ^ *b
+    }
+}
+
+// gcd(0, x) == x, so 0 is the identity
+impl<T: Copy + PartialEq + Default + Rem<Output = T>> Monoid<T> for Gcd {
+    fn id() -> T {
+        T::default()
+    }
+    fn op(a: &T, b: &T) -> T {
+        gcd(*a, *b)
+    }
+}
+
+impl<T: Copy + PartialEq + Default + Rem<Output = T>> Idempotent<T> for Gcd {}
+
+impl<T: PrimInt + BitAnd<Output = T>> Monoid<T> for And {
+    fn id() -> T {
+        // all ones: MAX for unsigned, -1 (= MIN | MAX) for signed
+        if T::SIGNED { T::MIN.checked_add(T::MAX).unwrap() } else { T::MAX }
+    }
+    fn op(a: &T, b: &T) -> T {
+        *a & *b
+    }
+}
+
+impl<T: PrimInt + BitAnd<Output = T>> Idempotent<T> for And {}
+
+impl<T: Copy + Default + BitOr<Output = T>> Monoid<T> for Or {
+    fn id() -> T {
+        T::default()
+    }
+    fn op(a: &T, b: &T) -> T {
+        *a | *b
+    }
+}
+
+impl<T: Copy + Default + BitOr<Output = T>> Idempotent<T> for Or {}
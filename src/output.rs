@@ -0,0 +1,95 @@
+// fast output for competitive programming
+// everything goes through one BufWriter, flushed once when the Output is dropped
+// (by BufWriter's own drop, which ignores errors; call flush() to see them)
+
+use std::fmt::{self, Display};
+use std::io::{self, BufWriter, StdoutLock, Write};
+
+/// Buffered writer for solution output.
+///
+/// Usage:
+/// ```
+/// use rust_files::{outln, output::Output};
+/// let mut buf = Vec::new();
+/// {
+///     let mut out = Output::new(&mut buf);
+///     outln!(out, "{}", 42);
+///     out.yes_no(true);
+///     out.join([1, 2, 3]);
+/// } // flushed here
+/// assert_eq!(String::from_utf8(buf).unwrap(), "42\nYES\n1 2 3\n");
+/// ```
+pub struct Output<W: Write> {
+    w: BufWriter<W>,
+}
+
+impl Output<StdoutLock<'static>> {
+    /// Locks stdout once for the whole run.
+    pub fn stdout() -> Self {
+        Output::new(io::stdout().lock())
+    }
+}
+
+impl<W: Write> Output<W> {
+    pub fn new(w: W) -> Self {
+        Output { w: BufWriter::new(w) }
+    }
+
+    /// Lets `write!`/`out!` target an `Output` directly.
+    /// Panics on an I/O error from the underlying writer (e.g. stdout closed), as `print!` does.
+    pub fn write_fmt(&mut self, args: fmt::Arguments) {
+        self.w.write_fmt(args).expect("failed to write output");
+    }
+
+    /// Prints `YES` or `NO` on its own line.
+    pub fn yes_no(&mut self, b: bool) {
+        self.write_fmt(format_args!("{}\n", if b { "YES" } else { "NO" }));
+    }
+
+    pub fn yes(&mut self) {
+        self.yes_no(true);
+    }
+
+    pub fn no(&mut self) {
+        self.yes_no(false);
+    }
+
+    /// Prints the items separated by spaces, then a newline. Works for a `Vec`, a slice or any iterator.
+    pub fn join<I>(&mut self, items: I)
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        for (i, x) in items.into_iter().enumerate() {
+            if i > 0 {
+                self.write_fmt(format_args!(" "));
+            }
+            self.write_fmt(format_args!("{}", x));
+        }
+        self.write_fmt(format_args!("\n"));
+    }
+
+    /// Panics on an I/O error, like [`write_fmt`](Output::write_fmt).
+    pub fn flush(&mut self) {
+        self.w.flush().expect("failed to flush output");
+    }
+}
+
+/// `write!` for an [`Output`]: `out!(out, "{} ", x);`
+#[macro_export]
+macro_rules! out {
+    ($out:expr, $($arg:tt)*) => {
+        $out.write_fmt(format_args!($($arg)*))
+    };
+}
+
+/// `writeln!` for an [`Output`]: `outln!(out, "{}", ans);` or `outln!(out);`
+#[macro_export]
+macro_rules! outln {
+    ($out:expr) => {
+        $out.write_fmt(format_args!("\n"))
+    };
+    ($out:expr, $($arg:tt)*) => {
+        $out.write_fmt(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
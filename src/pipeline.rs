@@ -0,0 +1,238 @@
+// async stages chained by bounded channels, the mpsc producer/consumer from 07e made reusable:
+//
+//   source -> [parse x4] -> [transform x2] -> [write x1]
+//                 \______________\________________\___ errors -> dead letters
+//
+// a full channel makes the stage before it wait (backpressure), a stage ends when its input
+// closes and all its workers are done, which closes its output, so everything shuts down in
+// order once the source runs dry
+
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tokio::sync::{Mutex, mpsc};
+use tokio::task::JoinSet;
+
+/// An item a stage failed on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeadLetter {
+    pub stage: &'static str,
+    /// The input item, `{:?}`-formatted (items of different stages have different types).
+    pub item: String,
+    pub error: String,
+}
+
+impl fmt::Display for DeadLetter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.stage, self.item, self.error)
+    }
+}
+
+/// Items that went through / failed in one stage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StageReport {
+    pub name: &'static str,
+    pub ok: u64,
+    pub failed: u64,
+}
+
+/// What a finished pipeline did.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    pub stages: Vec<StageReport>,
+    /// Dead letters nobody took with [`Pipeline::dead_letters`].
+    pub dead_letters: Vec<DeadLetter>,
+}
+
+impl Report {
+    pub fn stage(&self, name: &str) -> Option<&StageReport> {
+        self.stages.iter().find(|s| s.name == name)
+    }
+}
+
+struct Counters {
+    name: &'static str,
+    ok: AtomicU64,
+    failed: AtomicU64,
+}
+
+/// A running pipeline whose last stage produces `T`. Built from a source with
+/// [`from_iter`](Pipeline::from_iter) / [`from_receiver`](Pipeline::from_receiver),
+/// extended with [`stage`](Pipeline::stage), ended with [`sink`](Pipeline::sink) or [`collect`](Pipeline::collect).
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use rust_files::pipeline::Pipeline;
+/// let (mut out, report) = Pipeline::from_iter(8, vec!["1", "2", "x", "4"])
+///     .stage("parse", 2, 8, |s: &str| async move { s.parse::<i32>() })
+///     .stage("square", 1, 8, |n| async move { Ok::<_, String>(n * n) })
+///     .collect()
+///     .await;
+/// out.sort();
+/// assert_eq!(out, vec![1, 4, 16]);
+/// assert_eq!(report.dead_letters[0].stage, "parse");
+/// # }
+/// ```
+pub struct Pipeline<T> {
+    rx: mpsc::Receiver<T>,
+    tasks: JoinSet<()>,
+    stages: Vec<Arc<Counters>>,
+    dead_tx: mpsc::UnboundedSender<DeadLetter>,
+    dead_rx: Option<mpsc::UnboundedReceiver<DeadLetter>>,
+}
+
+impl<T: Send + 'static> Pipeline<T> {
+    /// Pipeline reading from an existing channel; it closes when all senders are dropped.
+    pub fn from_receiver(rx: mpsc::Receiver<T>) -> Self {
+        let (dead_tx, dead_rx) = mpsc::unbounded_channel();
+        Pipeline { rx, tasks: JoinSet::new(), stages: Vec::new(), dead_tx, dead_rx: Some(dead_rx) }
+    }
+
+    /// Pipeline fed by a task that sends the items of `iter` into a channel of `capacity`.
+    pub fn from_iter<I>(capacity: usize, iter: I) -> Self
+    where
+        I: IntoIterator<Item = T> + Send + 'static,
+        I::IntoIter: Send,
+    {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        let mut p = Pipeline::from_receiver(rx);
+        p.tasks.spawn(async move {
+            for item in iter {
+                if tx.send(item).await.is_err() {
+                    break; // everything downstream is gone
+                }
+            }
+        });
+        p
+    }
+
+    /// The dead-letter channel, to watch failures while the pipeline runs.
+    /// Once taken, they no longer show up in [`Report::dead_letters`].
+    pub fn dead_letters(&mut self) -> mpsc::UnboundedReceiver<DeadLetter> {
+        self.dead_rx.take().expect("dead letters already taken")
+    }
+
+    /// Adds a stage of `workers` tasks running `f` on every item, feeding a channel of `capacity`.
+    /// `Ok` goes on to the next stage, `Err` to the dead letters. With more than one worker the
+    /// output order is the order in which items finish.
+    pub fn stage<U, E, F, Fut>(self, name: &'static str, workers: usize, capacity: usize, f: F) -> Pipeline<U>
+    where
+        T: fmt::Debug,
+        U: Send + 'static,
+        E: fmt::Display,
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<U, E>> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        let Pipeline { rx: input, mut tasks, mut stages, dead_tx, dead_rx } = self;
+        let step = move |item, tx: mpsc::Sender<U>| {
+            let fut = f(item);
+            async move {
+                let out = fut.await.map_err(|e| e.to_string())?;
+                Ok(tx.send(out).await.is_ok())
+            }
+        };
+        stages.push(spawn_workers(&mut tasks, &dead_tx, input, name, workers, step, tx));
+        Pipeline { rx, tasks, stages, dead_tx, dead_rx }
+    }
+
+    /// Ends the pipeline with `workers` tasks consuming the items, and waits until everything is
+    /// through. Panics if a stage panicked.
+    pub async fn sink<E, F, Fut>(self, name: &'static str, workers: usize, f: F) -> Report
+    where
+        T: fmt::Debug,
+        E: fmt::Display,
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+    {
+        let Pipeline { rx: input, mut tasks, mut stages, dead_tx, dead_rx } = self;
+        let step = move |item, _: ()| {
+            let fut = f(item);
+            async move { fut.await.map(|()| true).map_err(|e| e.to_string()) }
+        };
+        stages.push(spawn_workers(&mut tasks, &dead_tx, input, name, workers, step, ()));
+        finish(tasks, &stages, dead_tx, dead_rx).await
+    }
+
+    /// Ends the pipeline by gathering the output. Panics if a stage panicked.
+    pub async fn collect(mut self) -> (Vec<T>, Report) {
+        let mut out = Vec::new();
+        while let Some(item) = self.rx.recv().await {
+            out.push(item);
+        }
+        (out, finish(self.tasks, &self.stages, self.dead_tx, self.dead_rx).await)
+    }
+}
+
+// `workers` tasks sharing `input`; `step` returns Ok(false) once its output is closed
+fn spawn_workers<T, O, S, Fut>(
+    tasks: &mut JoinSet<()>,
+    dead: &mpsc::UnboundedSender<DeadLetter>,
+    input: mpsc::Receiver<T>,
+    name: &'static str,
+    workers: usize,
+    step: S,
+    out: O,
+) -> Arc<Counters>
+where
+    T: fmt::Debug + Send + 'static,
+    O: Clone + Send + 'static,
+    S: Fn(T, O) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<bool, String>> + Send + 'static,
+{
+    let input = Arc::new(Mutex::new(input));
+    let counters = Arc::new(Counters { name, ok: AtomicU64::new(0), failed: AtomicU64::new(0) });
+    let step = Arc::new(step);
+    for _ in 0..workers.max(1) {
+        let (input, step, out, dead, counters) = (input.clone(), step.clone(), out.clone(), dead.clone(), counters.clone());
+        tasks.spawn(async move {
+            loop {
+                // one worker waits on the channel, the others on the lock
+                let Some(item) = input.lock().await.recv().await else { break };
+                let shown = format!("{:?}", item);
+                match step(item, out.clone()).await {
+                    Ok(true) => {
+                        counters.ok.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Ok(false) => break, // the next stage is gone
+                    Err(error) => {
+                        counters.failed.fetch_add(1, Ordering::Relaxed);
+                        let _ = dead.send(DeadLetter { stage: name, item: shown, error });
+                    }
+                }
+            }
+        });
+    }
+    counters
+}
+
+// waits for every task, then gathers the counters and the dead letters still in the channel
+async fn finish(
+    mut tasks: JoinSet<()>,
+    stages: &[Arc<Counters>],
+    dead_tx: mpsc::UnboundedSender<DeadLetter>,
+    dead_rx: Option<mpsc::UnboundedReceiver<DeadLetter>>,
+) -> Report {
+    while let Some(res) = tasks.join_next().await {
+        if let Err(e) = res
+            && e.is_panic()
+        {
+            std::panic::resume_unwind(e.into_panic());
+        }
+    }
+    drop(dead_tx);
+    let mut dead_letters = Vec::new();
+    if let Some(mut rx) = dead_rx {
+        while let Ok(d) = rx.try_recv() {
+            dead_letters.push(d);
+        }
+    }
+    let stages = stages
+        .iter()
+        .map(|c| StageReport { name: c.name, ok: c.ok.load(Ordering::Relaxed), failed: c.failed.load(Ordering::Relaxed) })
+        .collect();
+    Report { stages, dead_letters }
+}
@@ -0,0 +1,127 @@
+// prefix / suffix folds and sparse tables over any operation from ops.rs
+// e.g. the suffix minimum array of 04b is PrefixAgg::<usize, Min>::new(&a).suffixes()
+
+use std::marker::PhantomData;
+
+use crate::ops::{Group, Idempotent, Monoid};
+
+/// All prefix and suffix folds of a slice, built in O(n).
+///
+/// ```
+/// use rust_files::ops::{Min, Sum};
+/// use rust_files::prefix::PrefixAgg;
+/// let a = [3u64, 1, 4, 1, 5];
+/// let sum = PrefixAgg::<u64, Sum>::new(&a);
+/// assert_eq!(sum.range(1, 4), 6); // 1 + 4 + 1
+/// let min = PrefixAgg::<u64, Min>::new(&a);
+/// assert_eq!(&min.suffixes()[..5], &[1, 1, 1, 1, 5]);
+/// ```
+pub struct PrefixAgg<T, O> {
+    pre: Vec<T>, // pre[i] = fold of a[..i], pre[0] = id
+    suf: Vec<T>, // suf[i] = fold of a[i..], suf[n] = id
+    _op: PhantomData<O>,
+}
+
+impl<T: Clone, O: Monoid<T>> PrefixAgg<T, O> {
+    pub fn new(a: &[T]) -> Self {
+        let n = a.len();
+        let mut pre = Vec::with_capacity(n + 1);
+        pre.push(O::id());
+        for x in a {
+            pre.push(O::op(&pre[pre.len() - 1], x));
+        }
+        let mut suf = vec![O::id(); n + 1];
+        for i in (0..n).rev() {
+            suf[i] = O::op(&a[i], &suf[i + 1]);
+        }
+        PrefixAgg { pre, suf, _op: PhantomData }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pre.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Fold of `a[..i]`.
+    pub fn prefix(&self, i: usize) -> T {
+        self.pre[i].clone()
+    }
+
+    /// Fold of `a[i..]`.
+    pub fn suffix(&self, i: usize) -> T {
+        self.suf[i].clone()
+    }
+
+    /// `n + 1` values, `prefixes()[i]` = fold of `a[..i]`.
+    pub fn prefixes(&self) -> &[T] {
+        &self.pre
+    }
+
+    /// `n + 1` values, `suffixes()[i]` = fold of `a[i..]`, the last one is the identity.
+    pub fn suffixes(&self) -> &[T] {
+        &self.suf
+    }
+
+    /// Fold of everything except `a[i]`, O(1) for any monoid (prefix before, suffix after).
+    pub fn except(&self, i: usize) -> T {
+        O::op(&self.pre[i], &self.suf[i + 1])
+    }
+}
+
+impl<T: Clone, O: Group<T>> PrefixAgg<T, O> {
+    /// Fold of `a[l..r]` in O(1), for invertible operations (sum, xor).
+    pub fn range(&self, l: usize, r: usize) -> T {
+        assert!(l <= r && r <= self.len(), "bad range {}..{}", l, r);
+        O::inv_op(&self.pre[r], &self.pre[l])
+    }
+}
+
+/// O(n log n) build, O(1) range query for idempotent operations (min, max, gcd, and, or).
+///
+/// ```
+/// use rust_files::ops::Max;
+/// use rust_files::prefix::SparseTable;
+/// let st = SparseTable::<i32, Max>::new(&[2, 7, 1, 8, 2, 8]);
+/// assert_eq!(st.query(0, 3), 7);
+/// assert_eq!(st.query(2, 4), 8);
+/// ```
+pub struct SparseTable<T, O> {
+    table: Vec<Vec<T>>, // table[k][i] = fold of a[i..i + 2^k]
+    _op: PhantomData<O>,
+}
+
+impl<T: Clone, O: Idempotent<T>> SparseTable<T, O> {
+    pub fn new(a: &[T]) -> Self {
+        let mut table = vec![a.to_vec()];
+        let mut k = 1;
+        while (1 << k) <= a.len() {
+            let prev = &table[k - 1];
+            let half = 1 << (k - 1);
+            let row = (0..=a.len() - (1 << k)).map(|i| O::op(&prev[i], &prev[i + half])).collect();
+            table.push(row);
+            k += 1;
+        }
+        SparseTable { table, _op: PhantomData }
+    }
+
+    pub fn len(&self) -> usize {
+        self.table[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Fold of `a[l..r]`; two overlapping power-of-two blocks cover the range. Empty range gives the identity.
+    pub fn query(&self, l: usize, r: usize) -> T {
+        assert!(l <= r && r <= self.len(), "bad range {}..{}", l, r);
+        if l == r {
+            return O::id();
+        }
+        let k = (usize::BITS - 1 - (r - l).leading_zeros()) as usize;
+        O::op(&self.table[k][l], &self.table[k][r - (1 << k)])
+    }
+}
@@ -0,0 +1,337 @@
+// fast input reader for competitive programming
+// one byte buffer, tokens are parsed straight from the buffer (no String per token)
+
+use std::cell::RefCell;
+use std::fmt;
+use std::io::{self, Read, StdinLock};
+
+use crate::digits::parse_bytes_radix;
+
+const BUF_SIZE: usize = 1 << 16; // 64 KiB, grows only if a single token is longer
+
+/// Byte-buffered whitespace tokenizer over any `Read` (stdin, a file, a `&[u8]`).
+///
+/// Usage:
+/// ```
+/// use rust_files::scanner::Scanner;
+/// let mut sc = Scanner::new("3\n1 2 3\n".as_bytes());
+/// let n: usize = sc.next();
+/// let a: Vec<i64> = sc.vec(n);
+/// assert_eq!(a, vec![1, 2, 3]);
+/// ```
+pub struct Scanner<R> {
+    reader: R,
+    buf: Vec<u8>,
+    pos: usize, // next unread byte
+    end: usize, // bytes [pos, end) are valid
+    eof: bool,
+    line: usize, // 1-based position of buf[pos], for error messages
+    column: usize,
+}
+
+/// Why a `try_*` read failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanError {
+    /// Input ended before the value was complete.
+    UnexpectedEof,
+    /// A token was found but is not a valid `expected` (e.g. `"12a"` as `i64`, or an overflow).
+    ParseFailure { token: String, expected: &'static str, line: usize, column: usize },
+}
+
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScanError::UnexpectedEof => write!(f, "unexpected end of input"),
+            ScanError::ParseFailure { token, expected, line, column } => {
+                write!(f, "line {}, column {}: cannot parse {:?} as {}", line, column, token, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScanError {}
+
+/// A token still inside the scanner buffer, with the line/column it started at.
+pub struct Token<'a> {
+    pub bytes: &'a [u8],
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Token<'_> {
+    /// Builds the `ParseFailure` for this token.
+    pub fn fail<T>(&self) -> ScanError {
+        ScanError::ParseFailure {
+            token: String::from_utf8_lossy(self.bytes).into_owned(),
+            expected: std::any::type_name::<T>(),
+            line: self.line,
+            column: self.column,
+        }
+    }
+}
+
+impl<R: Read> Scanner<R> {
+    pub fn new(reader: R) -> Self {
+        Scanner { reader, buf: vec![0; BUF_SIZE], pos: 0, end: 0, eof: false, line: 1, column: 1 }
+    }
+
+    // moves the unread bytes to the front and reads more, returns false if nothing new came in
+    fn fill(&mut self) -> bool {
+        if self.eof {
+            return false;
+        }
+        if self.pos > 0 {
+            self.buf.copy_within(self.pos..self.end, 0);
+            self.end -= self.pos;
+            self.pos = 0;
+        }
+        if self.end == self.buf.len() {
+            self.buf.resize(self.buf.len() * 2, 0); // token longer than the buffer
+        }
+        loop {
+            match self.reader.read(&mut self.buf[self.end..]) {
+                Ok(0) => {
+                    self.eof = true;
+                    return false;
+                }
+                Ok(n) => {
+                    self.end += n;
+                    return true;
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => panic!("failed to read input: {}", e),
+            }
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        if self.pos == self.end && !self.fill() {
+            return None;
+        }
+        Some(self.buf[self.pos])
+    }
+
+    // consumes one (whitespace) byte, keeping line/column in step
+    fn bump(&mut self, b: u8) {
+        self.pos += 1;
+        if b == b'\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b) = self.peek() {
+            if !b.is_ascii_whitespace() {
+                break;
+            }
+            self.bump(b);
+        }
+    }
+
+    /// Next whitespace separated token as raw bytes, `None` at end of input.
+    /// The slice borrows the internal buffer, so it is valid until the next read.
+    pub fn token(&mut self) -> Option<&[u8]> {
+        self.token_at().map(|t| t.bytes)
+    }
+
+    /// Like [`Scanner::token`], but also says where the token started. Used by `Scan` impls.
+    pub fn token_at(&mut self) -> Option<Token<'_>> {
+        self.skip_whitespace();
+        self.peek()?;
+        let (line, column) = (self.line, self.column);
+        let mut len = 0;
+        loop {
+            if self.pos + len == self.end && !self.fill() {
+                break; // last token without trailing newline
+            }
+            if self.buf[self.pos + len].is_ascii_whitespace() {
+                break;
+            }
+            len += 1;
+        }
+        let start = self.pos;
+        self.pos += len;
+        self.column += len;
+        Some(Token { bytes: &self.buf[start..start + len], line, column })
+    }
+
+    /// Reads one value: integers, floats, `char`, `String`, or a tuple of those.
+    pub fn try_next<T: Scan>(&mut self) -> Result<T, ScanError> {
+        T::scan(self)
+    }
+
+    /// Reads `n` values into a `Vec`, stopping at the first error.
+    pub fn try_vec<T: Scan>(&mut self, n: usize) -> Result<Vec<T>, ScanError> {
+        (0..n).map(|_| self.try_next()).collect()
+    }
+
+    /// Reads all tokens of the next non-empty line, like the old `scanln!`.
+    pub fn try_line<T: Scan>(&mut self) -> Result<Vec<T>, ScanError> {
+        self.skip_whitespace(); // also skips blank lines
+        if self.peek().is_none() {
+            return Err(ScanError::UnexpectedEof);
+        }
+        let mut v = Vec::new();
+        loop {
+            // stop at the newline, but not at other whitespace
+            while let Some(b) = self.peek() {
+                if b == b'\n' || !b.is_ascii_whitespace() {
+                    break;
+                }
+                self.bump(b);
+            }
+            match self.peek() {
+                None => break,
+                Some(b'\n') => {
+                    self.bump(b'\n');
+                    break;
+                }
+                Some(_) => v.push(self.try_next()?),
+            }
+        }
+        Ok(v)
+    }
+
+    // panicking versions, for solutions where bad input is a bug anyway
+
+    /// Same as [`Scanner::try_next`], panics with the [`ScanError`] message.
+    #[allow(clippy::should_implement_trait)] // `sc.next()` is the usual cp name, not an Iterator
+    pub fn next<T: Scan>(&mut self) -> T {
+        self.try_next().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Reads `n` values into a `Vec`.
+    pub fn vec<T: Scan>(&mut self, n: usize) -> Vec<T> {
+        self.try_vec(n).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Next token as bytes, e.g. for a string problem.
+    pub fn bytes(&mut self) -> Vec<u8> {
+        self.token().unwrap_or_else(|| panic!("{}", ScanError::UnexpectedEof)).to_vec()
+    }
+
+    /// Next token as chars.
+    pub fn chars(&mut self) -> Vec<char> {
+        self.next::<String>().chars().collect()
+    }
+
+    /// Reads `rows` tokens as a char grid, e.g. a map of `#` and `.`.
+    pub fn grid(&mut self, rows: usize) -> Vec<Vec<char>> {
+        (0..rows).map(|_| self.chars()).collect()
+    }
+
+    /// Reads all tokens of the next non-empty line, like the old `scanln!`.
+    pub fn line<T: Scan>(&mut self) -> Vec<T> {
+        self.try_line().unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+/// Types that can be read from a [`Scanner`].
+pub trait Scan: Sized {
+    fn scan<R: Read>(sc: &mut Scanner<R>) -> Result<Self, ScanError>;
+}
+
+fn next_token<R: Read>(sc: &mut Scanner<R>) -> Result<Token<'_>, ScanError> {
+    sc.token_at().ok_or(ScanError::UnexpectedEof)
+}
+
+// integers are parsed straight from the buffer, with the same rules as str::parse (see digits.rs)
+macro_rules! impl_scan_int {
+    ($($t:ty),*) => {$(
+        impl Scan for $t {
+            fn scan<R: Read>(sc: &mut Scanner<R>) -> Result<Self, ScanError> {
+                let tok = next_token(sc)?;
+                parse_bytes_radix::<$t>(tok.bytes, 10).ok_or_else(|| tok.fail::<$t>())
+            }
+        }
+    )*};
+}
+
+impl_scan_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_scan_fromstr {
+    ($($t:ty),*) => {$(
+        impl Scan for $t {
+            fn scan<R: Read>(sc: &mut Scanner<R>) -> Result<Self, ScanError> {
+                let tok = next_token(sc)?;
+                std::str::from_utf8(tok.bytes)
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .ok_or_else(|| tok.fail::<$t>())
+            }
+        }
+    )*};
+}
+
+impl_scan_fromstr!(f32, f64, bool);
+
+impl Scan for String {
+    fn scan<R: Read>(sc: &mut Scanner<R>) -> Result<Self, ScanError> {
+        let tok = next_token(sc)?;
+        match std::str::from_utf8(tok.bytes) {
+            Ok(s) => Ok(s.to_owned()),
+            Err(_) => Err(tok.fail::<String>()),
+        }
+    }
+}
+
+impl Scan for char {
+    fn scan<R: Read>(sc: &mut Scanner<R>) -> Result<Self, ScanError> {
+        let tok = next_token(sc)?;
+        let mut it = std::str::from_utf8(tok.bytes).ok().map(|s| s.chars());
+        match it.as_mut().map(|c| (c.next(), c.next())) {
+            Some((Some(c), None)) => Ok(c),
+            _ => Err(tok.fail::<char>()),
+        }
+    }
+}
+
+macro_rules! impl_scan_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Scan),+> Scan for ($($name,)+) {
+            fn scan<R: Read>(sc: &mut Scanner<R>) -> Result<Self, ScanError> {
+                Ok(($($name::scan(sc)?,)+))
+            }
+        }
+    };
+}
+
+impl_scan_tuple!(A, B);
+impl_scan_tuple!(A, B, C);
+impl_scan_tuple!(A, B, C, D);
+impl_scan_tuple!(A, B, C, D, E);
+impl_scan_tuple!(A, B, C, D, E, F);
+
+// one scanner over stdin per thread, used by the next!/scanln! macros
+thread_local! {
+    static STDIN: RefCell<Scanner<StdinLock<'static>>> = RefCell::new(Scanner::new(io::stdin().lock()));
+}
+
+/// Runs `f` with the shared stdin scanner.
+pub fn with_stdin<T>(f: impl FnOnce(&mut Scanner<StdinLock<'static>>) -> T) -> T {
+    STDIN.with(|sc| f(&mut sc.borrow_mut()))
+}
+
+/// Reads one token from stdin.
+/// Usage: `let n: usize = next!();` or `let n = next!(usize);`
+#[macro_export]
+macro_rules! next {
+    () => {
+        $crate::scanner::with_stdin(|sc| sc.next())
+    };
+    ($t:ty) => {
+        $crate::scanner::with_stdin(|sc| sc.next::<$t>())
+    };
+}
+
+/// Reads the next non-empty line from stdin into a `Vec<T>`.
+/// Usage: `let row = scanln!(i32);` or single element, `let st = scanln!(String)[0];`
+#[macro_export]
+macro_rules! scanln {
+    ($t:ty) => {
+        $crate::scanner::with_stdin(|sc| sc.line::<$t>())
+    };
+}
@@ -0,0 +1,303 @@
+// named async jobs on a timer, the grown-up version of 07e's background_job loop:
+//   Schedule::once(after)        run once, `after` from now
+//   Schedule::every(period)      run every `period`, first run right away
+//   Schedule::daily_at(time)     run every day at local `time`, the wait is worked out again from
+//                                the wall clock before each run so it doesn't drift
+// a run that takes longer than the period makes the next ticks late, `on_missed` decides what then:
+//   Skip   run once right away for all the missed ticks, then back on the original grid (default)
+//   Burst  run the missed ones back to back until caught up
+//   Delay  run right away, then count the period from there
+// (daily jobs always wait for the next `time` after a run, so `on_missed` doesn't apply to them)
+// a job never overlaps itself; pausing drops the ticks that come by meanwhile
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{NaiveDateTime, NaiveTime};
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+use tokio::time::MissedTickBehavior;
+
+use crate::cancel::CancelToken;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MissedTick {
+    Skip,
+    Burst,
+    Delay,
+}
+
+impl From<MissedTick> for MissedTickBehavior {
+    fn from(m: MissedTick) -> Self {
+        match m {
+            MissedTick::Skip => MissedTickBehavior::Skip,
+            MissedTick::Burst => MissedTickBehavior::Burst,
+            MissedTick::Delay => MissedTickBehavior::Delay,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum When {
+    Once { after: Duration },
+    Every { period: Duration },
+    Daily { at: NaiveTime },
+}
+
+/// When a job runs, see the top of this file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Schedule {
+    pub when: When,
+    pub missed: MissedTick,
+}
+
+impl Schedule {
+    pub fn once(after: Duration) -> Self {
+        Schedule { when: When::Once { after }, missed: MissedTick::Skip }
+    }
+
+    /// Panics on a zero period.
+    pub fn every(period: Duration) -> Self {
+        assert!(!period.is_zero(), "period must be > 0");
+        Schedule { when: When::Every { period }, missed: MissedTick::Skip }
+    }
+
+    pub fn daily_at(at: NaiveTime) -> Self {
+        Schedule { when: When::Daily { at }, missed: MissedTick::Skip }
+    }
+
+    pub fn on_missed(mut self, missed: MissedTick) -> Self {
+        self.missed = missed;
+        self
+    }
+}
+
+/// Time from `now` to the next `at` o'clock, today if still ahead, else tomorrow.
+pub fn until_next(at: NaiveTime, now: NaiveDateTime) -> Duration {
+    let today = now.date().and_time(at);
+    let next = if today > now { today } else { today + chrono::Duration::days(1) };
+    (next - now).to_std().expect("next is after now")
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SchedulerError {
+    /// A job with this name is already scheduled.
+    Duplicate(String),
+    /// `shutdown` was called.
+    ShutDown,
+}
+
+impl fmt::Display for SchedulerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SchedulerError::Duplicate(name) => write!(f, "job {:?} already exists", name),
+            SchedulerError::ShutDown => write!(f, "scheduler is shut down"),
+        }
+    }
+}
+
+impl std::error::Error for SchedulerError {}
+
+/// A job as listed by [`Scheduler::list`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JobInfo {
+    pub name: String,
+    pub schedule: Schedule,
+    pub paused: bool,
+    pub running: bool,
+    /// Finished runs, panicked ones included.
+    pub runs: u64,
+}
+
+type JobFn = Arc<dyn Fn() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+
+struct Job {
+    schedule: Schedule,
+    paused: watch::Sender<bool>,
+    running: AtomicBool,
+    runs: AtomicU64,
+    token: CancelToken,
+}
+
+/// Runs named jobs on their [`Schedule`]s, on the current tokio runtime.
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread", start_paused = true)]
+/// # async fn main() {
+/// use std::time::Duration;
+/// use rust_files::scheduler::{Schedule, Scheduler};
+/// let sched = Scheduler::new();
+/// sched.add("tick", Schedule::every(Duration::from_secs(1)), || async { println!("tick") }).unwrap();
+/// tokio::time::sleep(Duration::from_millis(2500)).await;
+/// assert_eq!(sched.list()[0].runs, 3); // at 0s, 1s, 2s
+/// sched.shutdown().await;
+/// # }
+/// ```
+pub struct Scheduler {
+    jobs: Arc<Mutex<BTreeMap<String, Arc<Job>>>>,
+    tasks: Mutex<JoinSet<()>>,
+    token: CancelToken,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Scheduler::new()
+    }
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Scheduler { jobs: Arc::default(), tasks: Mutex::new(JoinSet::new()), token: CancelToken::new() }
+    }
+
+    /// Schedules `job` under `name`. A job that panics is counted as run and scheduled again.
+    pub fn add<F, Fut>(&self, name: &str, schedule: Schedule, job: F) -> Result<(), SchedulerError>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        if self.token.is_cancelled() {
+            return Err(SchedulerError::ShutDown);
+        }
+        let mut jobs = self.jobs.lock().unwrap();
+        if jobs.contains_key(name) {
+            return Err(SchedulerError::Duplicate(name.to_string()));
+        }
+        let entry = Arc::new(Job {
+            schedule,
+            paused: watch::Sender::new(false),
+            running: AtomicBool::new(false),
+            runs: AtomicU64::new(0),
+            token: self.token.child(),
+        });
+        jobs.insert(name.to_string(), entry.clone());
+        let run: JobFn = Arc::new(move || Box::pin(job()));
+        let (jobs, name) = (self.jobs.clone(), name.to_string());
+        let mut tasks = self.tasks.lock().unwrap();
+        // finished jobs (once, cancelled) stay in the set until joined, drop them here
+        while tasks.try_join_next().is_some() {}
+        tasks.spawn(async move {
+            drive(&entry, run).await;
+            // gone from the list once done, unless it was replaced meanwhile
+            let mut jobs = jobs.lock().unwrap();
+            if jobs.get(&name).is_some_and(|j| Arc::ptr_eq(j, &entry)) {
+                jobs.remove(&name);
+            }
+        });
+        Ok(())
+    }
+
+    /// Stops running `name` until [`resume`](Scheduler::resume). False if there is no such job.
+    pub fn pause(&self, name: &str) -> bool {
+        self.with_job(name, |j| {
+            j.paused.send_replace(true);
+        })
+    }
+
+    pub fn resume(&self, name: &str) -> bool {
+        self.with_job(name, |j| {
+            j.paused.send_replace(false);
+        })
+    }
+
+    /// Removes `name`; a run in progress still finishes. False if there is no such job.
+    pub fn cancel(&self, name: &str) -> bool {
+        match self.jobs.lock().unwrap().remove(name) {
+            Some(j) => {
+                j.token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The scheduled jobs, by name.
+    pub fn list(&self) -> Vec<JobInfo> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, j)| JobInfo {
+                name: name.clone(),
+                schedule: j.schedule,
+                paused: *j.paused.borrow(),
+                running: j.running.load(Ordering::SeqCst),
+                runs: j.runs.load(Ordering::SeqCst),
+            })
+            .collect()
+    }
+
+    /// Stops every job and returns once the runs in progress are finished.
+    pub async fn shutdown(&self) {
+        self.token.cancel();
+        let mut tasks = std::mem::take(&mut *self.tasks.lock().unwrap());
+        while tasks.join_next().await.is_some() {}
+        self.jobs.lock().unwrap().clear();
+    }
+
+    fn with_job(&self, name: &str, f: impl FnOnce(&Job)) -> bool {
+        self.jobs.lock().unwrap().get(name).map(|j| f(j)).is_some()
+    }
+}
+
+// waits for each tick of the job's schedule and runs it, until the job is cancelled
+async fn drive(job: &Job, run: JobFn) {
+    let token = &job.token;
+    let mut interval = match job.schedule.when {
+        When::Once { after } => {
+            if token.run_until_cancelled(tokio::time::sleep(after)).await.is_none() {
+                return;
+            }
+            // due while paused: wait for resume
+            let mut paused = job.paused.subscribe();
+            if token.run_until_cancelled(paused.wait_for(|p| !p)).await.is_none() {
+                return;
+            }
+            run_once(job, &run).await;
+            return;
+        }
+        When::Every { period } => tokio::time::interval(period),
+        When::Daily { at } => {
+            drive_daily(job, &run, at).await;
+            return;
+        }
+    };
+    interval.set_missed_tick_behavior(job.schedule.missed.into());
+    while token.run_until_cancelled(interval.tick()).await.is_some() {
+        if *job.paused.borrow() {
+            continue;
+        }
+        run_once(job, &run).await;
+    }
+}
+
+// a fixed 24h interval drifts off the wall clock (timer slack, suspend, dst), so each wait is
+// worked out from the local time again
+async fn drive_daily(job: &Job, run: &JobFn, at: NaiveTime) {
+    let mut last = NaiveDateTime::MIN;
+    loop {
+        // never earlier than the run just done, in case the timer woke a bit before `at`
+        let now = chrono::Local::now().naive_local().max(last);
+        let wait = until_next(at, now);
+        if job.token.run_until_cancelled(tokio::time::sleep(wait)).await.is_none() {
+            return;
+        }
+        last = now + wait;
+        if !*job.paused.borrow() {
+            run_once(job, run).await;
+        }
+    }
+}
+
+// the run gets its own task so a panic doesn't take the schedule down with it
+async fn run_once(job: &Job, run: &JobFn) {
+    job.running.store(true, Ordering::SeqCst);
+    let _ = tokio::spawn(run()).await; // a panic was already printed by the panic hook
+    job.runs.fetch_add(1, Ordering::SeqCst);
+    job.running.store(false, Ordering::SeqCst);
+}
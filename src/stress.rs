@@ -0,0 +1,248 @@
+// stress testing: compare a fast solution with a slow but obviously correct one on random inputs
+// on the first mismatch the input is shrunk (made smaller while it still fails) before reporting
+
+use std::fmt::{self, Debug};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// Settings for a stress run.
+///
+/// Usage:
+/// ```
+/// use rand::Rng;
+/// use rust_files::stress::Stress;
+/// let res = Stress::new().cases(500).seed(7).run(
+///     |rng| (0..rng.gen_range(0..10)).map(|_| rng.gen_range(-50..50)).collect::<Vec<i64>>(),
+///     |v| v.iter().sum::<i64>(),          // reference (brute force)
+///     |v| v.iter().fold(0, |a, x| a + x), // candidate
+/// );
+/// assert_eq!(res.unwrap(), 500);
+/// ```
+pub struct Stress {
+    cases: usize,
+    seed: u64,
+    max_shrink_steps: usize,
+}
+
+impl Default for Stress {
+    fn default() -> Self {
+        Stress { cases: 1000, seed: 0, max_shrink_steps: 1000 }
+    }
+}
+
+impl Stress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of random cases (default 1000).
+    pub fn cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Base seed; case `i` is generated from `seed + i` (default 0).
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Upper bound on shrink attempts, so a slow reference can't hang the run (default 1000).
+    pub fn max_shrink_steps(mut self, steps: usize) -> Self {
+        self.max_shrink_steps = steps;
+        self
+    }
+
+    /// Runs every case, returns how many passed or the first (shrunk) failure.
+    pub fn run<I, O, G, R, C>(&self, generate: G, reference: R, candidate: C) -> Result<usize, Failure<I, O>>
+    where
+        I: Shrink + Clone + Debug,
+        O: PartialEq + Debug,
+        G: Fn(&mut StdRng) -> I,
+        R: Fn(&I) -> O,
+        C: Fn(&I) -> O,
+    {
+        for case in 0..self.cases {
+            let seed = self.seed.wrapping_add(case as u64);
+            let input = generate(&mut StdRng::seed_from_u64(seed));
+            if reference(&input) != candidate(&input) {
+                let fails = |x: &I| reference(x) != candidate(x);
+                let (shrunk, shrink_steps) = shrink(input.clone(), fails, self.max_shrink_steps);
+                return Err(Failure {
+                    case,
+                    seed,
+                    expected: reference(&shrunk),
+                    actual: candidate(&shrunk),
+                    original: input,
+                    input: shrunk,
+                    shrink_steps,
+                });
+            }
+        }
+        Ok(self.cases)
+    }
+
+    /// Same as [`Stress::run`], but panics with the failure report, for use in `main` or a `#[test]`.
+    pub fn check<I, O, G, R, C>(&self, generate: G, reference: R, candidate: C)
+    where
+        I: Shrink + Clone + Debug,
+        O: PartialEq + Debug,
+        G: Fn(&mut StdRng) -> I,
+        R: Fn(&I) -> O,
+        C: Fn(&I) -> O,
+    {
+        if let Err(f) = self.run(generate, reference, candidate) {
+            panic!("{}", f);
+        }
+    }
+}
+
+/// First mismatching case, after shrinking.
+#[derive(Debug, Clone)]
+pub struct Failure<I, O> {
+    pub case: usize,
+    pub seed: u64, // regenerate `original` with generate(&mut StdRng::seed_from_u64(seed))
+    pub original: I,
+    pub input: I, // shrunk version of `original`, still failing
+    pub expected: O,
+    pub actual: O,
+    pub shrink_steps: usize,
+}
+
+impl<I: Debug, O: Debug> fmt::Display for Failure<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "mismatch on case {} (seed {})", self.case, self.seed)?;
+        writeln!(f, "  original input: {:?}", self.original)?;
+        writeln!(f, "  shrunk input:   {:?} ({} shrink steps)", self.input, self.shrink_steps)?;
+        writeln!(f, "  expected:       {:?}", self.expected)?;
+        write!(f, "  actual:         {:?}", self.actual)
+    }
+}
+
+// greedy shrink: take the first smaller candidate that still fails, repeat until none does.
+// candidates are made one at a time, so a big input only pays for the ones actually tried
+fn shrink<I: Shrink>(mut input: I, fails: impl Fn(&I) -> bool, max_steps: usize) -> (I, usize) {
+    let mut steps = 0;
+    let mut attempts = 0;
+    loop {
+        let mut next = None;
+        for smaller in input.shrink() {
+            attempts += 1;
+            if attempts > max_steps {
+                break;
+            }
+            if fails(&smaller) {
+                next = Some(smaller);
+                break;
+            }
+        }
+        match next {
+            Some(smaller) => {
+                input = smaller;
+                steps += 1;
+            }
+            None => break,
+        }
+    }
+    (input, steps)
+}
+
+/// Inputs that can propose smaller versions of themselves, simplest first.
+/// The candidates are produced lazily; the shrinker stops at the first one that still fails.
+pub trait Shrink: Sized {
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_>;
+}
+
+// integers move toward 0: 0, then x - x/2, x - x/4, ..., x - 1
+macro_rules! impl_shrink_int {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+                let x = *self;
+                if x == 0 {
+                    return Box::new(std::iter::empty());
+                }
+                // the last d is +-1, so x - 1 toward zero is always tried
+                let halves = std::iter::successors(Some(x / 2), |&d| (d / 2 != 0).then_some(d / 2));
+                Box::new(std::iter::once(0).chain(halves.filter(|&d| d != 0).map(move |d| x - d)))
+            }
+        }
+    )*};
+}
+
+impl_shrink_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Shrink for bool {
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        Box::new(self.then_some(false).into_iter())
+    }
+}
+
+impl Shrink for char {
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        Box::new((*self != 'a').then_some('a').into_iter())
+    }
+}
+
+// ranges [start, end) of a sequence of n to cut out, biggest first: everything, then halves,
+// then aligned chunks of n/4, n/8, ..., down to single elements
+fn removals(n: usize) -> impl Iterator<Item = (usize, usize)> {
+    let sizes = std::iter::successors(Some(n / 2).filter(|&k| k > 0), |&k| (k > 1).then_some(k / 2));
+    let all = (n > 0).then_some((0, n));
+    all.into_iter().chain(sizes.flat_map(move |k| (0..n).step_by(k).map(move |s| (s, (s + k).min(n)))))
+}
+
+// vectors: drop everything, halves, smaller and smaller chunks, then shrink elements in place
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        let cut = removals(self.len()).map(move |(s, e)| [&self[..s], &self[e..]].concat());
+        let elems = (0..self.len()).flat_map(move |i| {
+            self[i].shrink().map(move |x| {
+                let mut w = self.clone();
+                w[i] = x;
+                w
+            })
+        });
+        Box::new(cut.chain(elems))
+    }
+}
+
+// same order as Vec<char>, working on the byte offsets of the chars
+impl Shrink for String {
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+        let mut at: Vec<usize> = self.char_indices().map(|(i, _)| i).collect();
+        at.push(self.len());
+        let n = at.len() - 1;
+        let bounds = at.clone();
+        let cut = removals(n).map(move |(s, e)| [&self[..bounds[s]], &self[bounds[e]..]].concat());
+        let elems = (0..n).flat_map(move |i| {
+            let (a, b) = (at[i], at[i + 1]);
+            let c = self[a..b].chars().next().unwrap();
+            c.shrink().map(move |x| format!("{}{}{}", &self[..a], x, &self[b..])).collect::<Vec<_>>()
+        });
+        Box::new(cut.chain(elems))
+    }
+}
+
+macro_rules! impl_shrink_tuple {
+    ($($name:ident $idx:tt),+) => {
+        impl<$($name: Shrink + Clone),+> Shrink for ($($name,)+) {
+            fn shrink(&self) -> Box<dyn Iterator<Item = Self> + '_> {
+                let it: Box<dyn Iterator<Item = Self> + '_> = Box::new(std::iter::empty());
+                $(
+                    let it: Box<dyn Iterator<Item = Self> + '_> = Box::new(it.chain(self.$idx.shrink().map(move |x| {
+                        let mut t = self.clone();
+                        t.$idx = x;
+                        t
+                    })));
+                )+
+                it
+            }
+        }
+    };
+}
+
+impl_shrink_tuple!(A 0, B 1);
+impl_shrink_tuple!(A 0, B 1, C 2);
+impl_shrink_tuple!(A 0, B 1, C 2, D 3);
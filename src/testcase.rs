@@ -0,0 +1,75 @@
+// sample test cases embedded in cp solutions
+// a solution keeps its samples in a trailing block comment that starts with a `testcase:` line:
+//   testcase:
+//   2
+//   1 2
+//   3 4
+//   <blank line>
+//   3
+//   7
+// input first, one blank line, then the expected output.
+// without a blank line the whole block is expected output (e.g. leetcode files that hard-code input in main).
+
+/// One sample: what to feed on stdin and what the solution must print.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestCase {
+    pub input: String,
+    pub expected: String,
+}
+
+/// Collects every `/* testcase: ... */` block of a source file, in order.
+pub fn extract(source: &str) -> Vec<TestCase> {
+    let mut cases = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("*/") else { break };
+        if let Some(case) = parse_block(&after[..end]) {
+            cases.push(case);
+        }
+        rest = &after[end + 2..];
+    }
+    cases
+}
+
+fn parse_block(block: &str) -> Option<TestCase> {
+    let mut lines = block.lines().skip_while(|l| l.trim().is_empty());
+    if lines.next()?.trim() != "testcase:" {
+        return None;
+    }
+    let body: Vec<&str> = lines.collect();
+    let (input, expected) = match body.iter().position(|l| l.trim().is_empty()) {
+        Some(i) => (&body[..i], &body[i + 1..]),
+        None => (&body[..0], &body[..]),
+    };
+    let join = |ls: &[&str]| ls.iter().map(|l| format!("{}\n", l)).collect::<String>();
+    Some(TestCase { input: join(input), expected: join(expected).trim_end().to_string() + "\n" })
+}
+
+/// First place where two outputs differ, comparing whitespace separated tokens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub index: usize, // 0-based token index
+    pub expected: Option<String>, // None = output has extra tokens
+    pub actual: Option<String>,   // None = output ended early
+}
+
+/// Compares token by token, so trailing spaces and line breaks don't matter.
+pub fn diff_tokens(expected: &str, actual: &str) -> Option<Mismatch> {
+    let mut e = expected.split_whitespace();
+    let mut a = actual.split_whitespace();
+    let mut index = 0;
+    loop {
+        match (e.next(), a.next()) {
+            (None, None) => return None,
+            (x, y) if x == y => index += 1,
+            (x, y) => {
+                return Some(Mismatch {
+                    index,
+                    expected: x.map(str::to_string),
+                    actual: y.map(str::to_string),
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+// tiny http/1.1 server answering GET /users and GET /users/{id} from users.json,
+// a snapshot of jsonplaceholder's 10 users; run it with `cargo run --bin mock_api`
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::OnceLock;
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::User;
+
+/// The fixture the mock serves, as json text.
+pub const FIXTURE: &str = include_str!("users.json");
+
+/// The fixture parsed.
+pub fn users() -> &'static [User] {
+    static USERS: OnceLock<Vec<User>> = OnceLock::new();
+    USERS.get_or_init(|| serde_json::from_str(FIXTURE).expect("users.json is not a list of users"))
+}
+
+/// Status line and json body for a request line like `GET /users/3 HTTP/1.1`.
+/// Unknown ids get 404 with `{}`, like the real api.
+pub fn route(method: &str, path: &str) -> (u16, String) {
+    if method != "GET" {
+        return (405, r#"{"error":"only GET"}"#.to_string());
+    }
+    let path = path.split('?').next().unwrap_or("").trim_end_matches('/');
+    let parts: Vec<&str> = path.split('/').skip(1).collect();
+    match parts.as_slice() {
+        ["users"] => (200, serde_json::to_string_pretty(users()).unwrap()),
+        ["users", id] => match id.parse::<u32>().ok().and_then(|id| users().iter().find(|u| u.id == id)) {
+            Some(u) => (200, serde_json::to_string_pretty(u).unwrap()),
+            None => (404, "{}".to_string()),
+        },
+        _ => (404, "{}".to_string()),
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "",
+    }
+}
+
+// one request per connection, then close: simple, and reqwest copes fine
+async fn handle(sock: TcpStream) -> io::Result<()> {
+    let mut reader = BufReader::new(sock);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+    let mut words = request_line.split_whitespace();
+    let (status, body) = match (words.next(), words.next()) {
+        (Some(method), Some(path)) => route(method, path),
+        _ => (400, r#"{"error":"bad request line"}"#.to_string()),
+    };
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        reason(status),
+        body.len()
+    );
+    let mut sock = reader.into_inner();
+    sock.write_all(head.as_bytes()).await?;
+    sock.write_all(body.as_bytes()).await?;
+    sock.shutdown().await
+}
+
+/// Answers connections on `listener` until the task is dropped.
+pub async fn serve(listener: TcpListener) -> io::Result<()> {
+    loop {
+        let (sock, _) = listener.accept().await?;
+        tokio::spawn(async move {
+            if let Err(e) = handle(sock).await {
+                eprintln!("mock_api: {}", e);
+            }
+        });
+    }
+}
+
+/// Binds `127.0.0.1` on a free port, serves in the background and returns the base url
+/// to give to [`UserClient::new`](super::UserClient::new), e.g. `http://127.0.0.1:40123`.
+pub async fn spawn_local() -> io::Result<String> {
+    let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await?;
+    let base = format!("http://{}", listener.local_addr()?);
+    tokio::spawn(serve(listener));
+    Ok(base)
+}
@@ -0,0 +1,88 @@
+// the /users api of jsonplaceholder.typicode.com (what 07f fetches) as typed structs,
+// plus a local copy of it in `mock` so demos and tests don't need the internet
+
+pub mod mock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::http::{HttpClient, HttpError};
+
+/// The real thing, `UserClient::new(users::PUBLIC_API)`.
+pub const PUBLIC_API: &str = "https://jsonplaceholder.typicode.com";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    pub id: u32,
+    pub name: String,
+    pub username: String,
+    pub email: String,
+    pub address: Address,
+    pub phone: String,
+    pub website: String,
+    pub company: Company,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Address {
+    pub street: String,
+    pub suite: String,
+    pub city: String,
+    pub zipcode: String,
+    pub geo: Geo,
+}
+
+/// Coordinates, kept as the strings the api sends (`"-37.3159"`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Geo {
+    pub lat: String,
+    pub lng: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Company {
+    pub name: String,
+    #[serde(rename = "catchPhrase")]
+    pub catch_phrase: String,
+    pub bs: String,
+}
+
+/// Typed access to `{base}/users` and `{base}/users/{id}`.
+///
+/// ```no_run
+/// # async fn demo() -> Result<(), rust_files::http::HttpError> {
+/// use rust_files::users::{UserClient, PUBLIC_API};
+/// let user = UserClient::new(PUBLIC_API).get_user(1).await?;
+/// println!("{} lives in {}", user.name, user.address.city);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct UserClient {
+    http: HttpClient,
+    base: String,
+}
+
+impl UserClient {
+    /// Client for the api at `base` (no trailing `/users`) on the shared [`HttpClient`].
+    pub fn new(base: &str) -> Self {
+        UserClient::with_client(HttpClient::shared().clone(), base)
+    }
+
+    /// Same, with your own timeouts and retries.
+    pub fn with_client(http: HttpClient, base: &str) -> Self {
+        UserClient { http, base: base.trim_end_matches('/').to_string() }
+    }
+
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    /// `GET /users/{id}`; an unknown id is `HttpError::Status` with 404.
+    pub async fn get_user(&self, id: u32) -> Result<User, HttpError> {
+        self.http.get_json(&format!("{}/users/{}", self.base, id)).await
+    }
+
+    /// `GET /users`.
+    pub async fn list_users(&self) -> Result<Vec<User>, HttpError> {
+        self.http.get_json(&format!("{}/users", self.base)).await
+    }
+}
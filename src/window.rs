@@ -0,0 +1,113 @@
+// sliding window / two pointer helpers
+// the window is [l, r) over a slice; callbacks keep whatever state the problem needs
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Longest window `[l, r)` for which `valid(&state)` holds, as `(l, len)`.
+/// `add`/`remove` update `state` when an item enters on the right / leaves on the left.
+/// Validity must be monotone: if a window is valid, every window inside it is too. O(n) calls.
+///
+/// ```
+/// use rust_files::window::longest_window;
+/// // longest run with sum <= 5
+/// let a = [4, 1, 1, 2, 1, 6, 1];
+/// let best = longest_window(&a, 0, |s, x| *s += x, |s, x| *s -= x, |s| *s <= 5);
+/// assert_eq!(best, (1, 4));
+/// ```
+pub fn longest_window<T, S>(
+    items: &[T],
+    mut state: S,
+    mut add: impl FnMut(&mut S, &T),
+    mut remove: impl FnMut(&mut S, &T),
+    valid: impl Fn(&S) -> bool,
+) -> (usize, usize) {
+    let mut best = (0, 0);
+    let mut l = 0;
+    for r in 0..items.len() {
+        add(&mut state, &items[r]);
+        while l <= r && !valid(&state) {
+            remove(&mut state, &items[l]);
+            l += 1;
+        }
+        if r + 1 - l > best.1 {
+            best = (l, r + 1 - l);
+        }
+    }
+    best
+}
+
+/// Calls `f` on every window of exactly `k` items, left to right, e.g. for sliding sums or
+/// distinct counts. Returns `items.len() - k + 1` results (none if `k` is 0 or too big).
+pub fn fixed_windows<T, S, R>(
+    items: &[T],
+    k: usize,
+    mut state: S,
+    mut add: impl FnMut(&mut S, &T),
+    mut remove: impl FnMut(&mut S, &T),
+    mut f: impl FnMut(&S) -> R,
+) -> Vec<R> {
+    let mut res = Vec::new();
+    if k == 0 || k > items.len() {
+        return res;
+    }
+    for r in 0..items.len() {
+        add(&mut state, &items[r]);
+        if r >= k {
+            remove(&mut state, &items[r - k]);
+        }
+        if r + 1 >= k {
+            res.push(f(&state));
+        }
+    }
+    res
+}
+
+/// Last index where each key was seen.
+pub struct LastSeen<K> {
+    map: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq> Default for LastSeen<K> {
+    fn default() -> Self {
+        LastSeen { map: HashMap::new() }
+    }
+}
+
+impl<K: Hash + Eq> LastSeen<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `key` at `index`, returns the index it was seen at before (if any).
+    pub fn see(&mut self, key: K, index: usize) -> Option<usize> {
+        self.map.insert(key, index)
+    }
+
+    pub fn get(&self, key: &K) -> Option<usize> {
+        self.map.get(key).copied()
+    }
+}
+
+/// Longest run of pairwise distinct items as `(start, len)`, O(n).
+/// The left end jumps straight past the previous copy instead of restarting the scan.
+pub fn longest_unique<T: Hash + Eq + Clone>(items: &[T]) -> (usize, usize) {
+    let mut seen = LastSeen::new();
+    let mut best = (0, 0);
+    let mut l = 0;
+    for (r, x) in items.iter().enumerate() {
+        if let Some(prev) = seen.see(x.clone(), r) {
+            l = l.max(prev + 1); // prev < l means that copy already left the window
+        }
+        if r + 1 - l > best.1 {
+            best = (l, r + 1 - l);
+        }
+    }
+    best
+}
+
+/// Leetcode 3 in O(n): length of the longest substring without repeating characters.
+pub fn longest_unique_substring(s: &str) -> usize {
+    let chars: Vec<char> = s.chars().collect();
+    longest_unique(&chars).1
+}
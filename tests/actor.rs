@@ -0,0 +1,146 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rust_files::actor::{self, Actor, ActorConfig, ActorError, Reply};
+use tokio::sync::Notify;
+
+#[derive(Debug)]
+enum Msg {
+    Add(u64),
+    Get(Reply<u64>),
+    Panic,
+    Block(Reply<()>, Arc<Notify>), // replies, then holds the actor until notified, to fill the mailbox
+}
+
+struct Counter(u64);
+
+impl Actor for Counter {
+    type Msg = Msg;
+
+    async fn handle(&mut self, msg: Msg) {
+        match msg {
+            Msg::Add(n) => self.0 += n,
+            Msg::Get(reply) => reply.send(self.0),
+            Msg::Panic => panic!("told to panic"),
+            Msg::Block(entered, go) => {
+                entered.send(());
+                go.notified().await;
+            }
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn counter_without_a_mutex() {
+    let counter = actor::spawn(ActorConfig::default(), || Counter(0));
+    let tasks: Vec<_> = (0..10)
+        .map(|_| {
+            let c = counter.clone();
+            tokio::spawn(async move {
+                for _ in 0..100 {
+                    c.tell(Msg::Add(1)).await.unwrap();
+                }
+            })
+        })
+        .collect();
+    for t in tasks {
+        t.await.unwrap();
+    }
+    assert_eq!(counter.ask(Msg::Get).await, Ok(1000));
+}
+
+#[tokio::test]
+async fn messages_are_handled_in_order() {
+    let counter = actor::spawn(ActorConfig::default(), || Counter(1));
+    counter.tell(Msg::Add(2)).await.unwrap();
+    let first = counter.ask(Msg::Get).await;
+    counter.tell(Msg::Add(3)).await.unwrap();
+    assert_eq!((first, counter.ask(Msg::Get).await), (Ok(3), Ok(6)));
+}
+
+#[tokio::test]
+async fn full_mailbox() {
+    let counter = actor::spawn(ActorConfig { mailbox: 2, ..ActorConfig::default() }, || Counter(0));
+    let go = Arc::new(Notify::new());
+    counter.ask(|r| Msg::Block(r, go.clone())).await.unwrap(); // the actor is busy, the mailbox empty
+    counter.try_tell(Msg::Add(1)).unwrap();
+    counter.try_tell(Msg::Add(1)).unwrap();
+    assert_eq!(counter.try_tell(Msg::Add(1)), Err(ActorError::MailboxFull));
+    go.notify_one();
+    assert_eq!(counter.ask(Msg::Get).await, Ok(2));
+}
+
+#[tokio::test]
+async fn panics_restart_with_fresh_state() {
+    let built = Arc::new(AtomicUsize::new(0));
+    let b = built.clone();
+    let counter = actor::spawn(ActorConfig::default(), move || {
+        b.fetch_add(1, Ordering::SeqCst);
+        Counter(100)
+    });
+    counter.tell(Msg::Add(5)).await.unwrap();
+    assert_eq!(counter.ask(Msg::Get).await, Ok(105));
+
+    counter.tell(Msg::Panic).await.unwrap();
+    counter.tell(Msg::Add(1)).await.unwrap(); // waits in the mailbox, handled by the new actor
+    assert_eq!(counter.ask(Msg::Get).await, Ok(101));
+    assert_eq!(counter.restarts(), 1);
+    assert_eq!(built.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn an_ask_that_panics_gets_no_reply() {
+    struct Grumpy;
+    impl Actor for Grumpy {
+        type Msg = Reply<()>;
+        async fn handle(&mut self, _reply: Reply<()>) {
+            panic!("no");
+        }
+    }
+    let grumpy = actor::spawn(ActorConfig { max_restarts: 5, ..ActorConfig::default() }, || Grumpy);
+    assert_eq!(grumpy.ask(|r| r).await, Err(ActorError::NoReply));
+    assert_eq!(grumpy.ask(|r| r).await, Err(ActorError::NoReply));
+    assert!(!grumpy.is_stopped());
+}
+
+#[tokio::test]
+async fn too_many_panics_stop_the_actor() {
+    let counter = actor::spawn(ActorConfig { max_restarts: 1, ..ActorConfig::default() }, || Counter(0));
+    counter.tell(Msg::Panic).await.unwrap();
+    assert_eq!(counter.ask(Msg::Get).await, Ok(0));
+    counter.tell(Msg::Panic).await.unwrap();
+    let after = counter.ask(Msg::Get).await;
+    assert!(matches!(after, Err(ActorError::NoReply | ActorError::Stopped)), "{:?}", after);
+    assert!(counter.is_stopped());
+    assert_eq!(counter.tell(Msg::Add(1)).await, Err(ActorError::Stopped));
+    assert_eq!(counter.restarts(), 1);
+}
+
+#[tokio::test]
+async fn stops_when_the_last_handle_is_dropped() {
+    struct Tracked(Arc<AtomicUsize>);
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+    impl Actor for Tracked {
+        type Msg = ();
+        async fn handle(&mut self, _: ()) {}
+    }
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let d = dropped.clone();
+    let h = actor::spawn(ActorConfig::default(), move || Tracked(d.clone()));
+    let h2 = h.clone();
+    h.tell(()).await.unwrap();
+    drop(h);
+    h2.tell(()).await.unwrap();
+    drop(h2);
+    for _ in 0..100 {
+        if dropped.load(Ordering::SeqCst) == 1 {
+            return;
+        }
+        tokio::task::yield_now().await;
+    }
+    panic!("actor still alive without handles");
+}
@@ -0,0 +1,189 @@
+// the timeline tables in the comments of 07b, 07c and 07d, checked on tokio's paused clock:
+// sleeps advance a virtual clock instead of waiting, so "20s" is exact and the test takes no time.
+// the functions are the ones from the binaries, pulled in by path (their main() goes unused)
+
+use std::sync::{Arc, Mutex};
+
+use tokio::time::{sleep, Duration, Instant};
+
+#[allow(dead_code)]
+#[path = "../src/bin/07b_function_async.rs"]
+mod b07;
+#[allow(dead_code)]
+#[path = "../src/bin/07c_async.rs"]
+mod c07;
+#[allow(dead_code)]
+#[path = "../src/bin/07d_async_tokio_join.rs"]
+mod d07;
+
+fn secs(s: f64) -> Duration {
+    Duration::from_secs_f64(s)
+}
+
+// (virtual time since `start`, what finished), in completion order
+#[derive(Clone)]
+struct Log {
+    start: Instant,
+    events: Arc<Mutex<Vec<(Duration, String)>>>,
+}
+
+impl Log {
+    fn new() -> Log {
+        Log { start: Instant::now(), events: Arc::default() }
+    }
+
+    fn record(&self, what: impl Into<String>) {
+        self.events.lock().unwrap().push((self.start.elapsed(), what.into()));
+    }
+
+    fn events(&self) -> Vec<(Duration, String)> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+fn ev(s: f64, what: &str) -> (Duration, String) {
+    (secs(s), what.to_string())
+}
+
+#[tokio::test(start_paused = true)]
+async fn b07_single_await() {
+    let log = Log::new();
+    let data = b07::fetch_data(42, 3000).await;
+    log.record(data);
+    assert_eq!(log.events(), vec![ev(3.0, "Data for id 42")]);
+}
+
+// 07c: | 0s futures created | 20s id 42 printed | 20s await data43 starts | 30s id 43 printed |
+#[tokio::test(start_paused = true)]
+async fn c07_sequential_awaits_add_up() {
+    let log = Log::new();
+    let data42 = c07::fetch_data(42, 20);
+    let data43 = c07::fetch_data(43, 10);
+    log.record("futures created");
+    log.record(data42.await);
+    log.record(data43.await);
+    assert_eq!(
+        log.events(),
+        vec![ev(0.0, "futures created"), ev(20.0, "Data for id 42"), ev(30.0, "Data for id 43")]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn c07_futures_are_lazy() {
+    // creating the future starts nothing: waiting 15s first does not shorten its 10s
+    let log = Log::new();
+    let data43 = c07::fetch_data(43, 10);
+    sleep(secs(15.0)).await;
+    log.record(data43.await);
+    assert_eq!(log.events(), vec![ev(25.0, "Data for id 43")]);
+}
+
+// 07d: | 0s both start | 5s id 43 ready | 10s id 42 ready | 10s both printed |
+#[tokio::test(start_paused = true)]
+async fn d07_join_runs_both_at_once() {
+    let log = Log::new();
+    let (l42, l43) = (log.clone(), log.clone());
+    let (r42, r43) = tokio::join!(
+        async move {
+            let r = d07::fetch_data(42, 10).await;
+            l42.record("id 42 ready");
+            r
+        },
+        async move {
+            let r = d07::fetch_data(43, 5).await;
+            l43.record("id 43 ready");
+            r
+        }
+    );
+    log.record(format!("received {} / {}", r42, r43));
+    assert_eq!(
+        log.events(),
+        vec![
+            ev(5.0, "id 43 ready"),
+            ev(10.0, "id 42 ready"),
+            ev(10.0, "received Data for id 42 / Data for id 43"),
+        ]
+    );
+}
+
+// 07d: user (3s), orders (5s) and prefs (100ms) concurrently -> all three after 5s
+#[tokio::test(start_paused = true)]
+async fn d07_join_three_takes_the_longest() {
+    let log = Log::new();
+    let (lu, lo, lp) = (log.clone(), log.clone(), log.clone());
+    let (user, orders, prefs) = tokio::join!(
+        async move {
+            let u = d07::fetch_user(44).await;
+            lu.record("user");
+            u
+        },
+        async move {
+            let o = d07::fetch_orders(44).await;
+            lo.record("orders");
+            o
+        },
+        async move {
+            sleep(Duration::from_millis(100)).await;
+            lp.record("prefs");
+            "Preferences".to_string()
+        }
+    );
+    assert_eq!(user, "User 44");
+    assert_eq!(orders, vec!["Order for user 44".to_string()]);
+    assert_eq!(prefs, "Preferences");
+    assert_eq!(log.events(), vec![ev(0.1, "prefs"), ev(3.0, "user"), ev(5.0, "orders")]);
+    assert_eq!(log.start.elapsed(), secs(5.0));
+}
+
+// the spawn version of 07c: tasks start right away, so 43 is done at 10s even though
+// main awaits 42 first; main sees both at 20s
+#[tokio::test(start_paused = true)]
+async fn spawned_tasks_start_eagerly() {
+    let log = Log::new();
+    let (l42, l43) = (log.clone(), log.clone());
+    let h42 = tokio::spawn(async move {
+        let r = c07::fetch_data(42, 20).await;
+        l42.record("task 42 done");
+        r
+    });
+    let h43 = tokio::spawn(async move {
+        let r = c07::fetch_data(43, 10).await;
+        l43.record("task 43 done");
+        r
+    });
+    log.record(format!("main received {}", h42.await.unwrap()));
+    log.record(format!("main received {}", h43.await.unwrap()));
+    assert_eq!(
+        log.events(),
+        vec![
+            ev(10.0, "task 43 done"),
+            ev(20.0, "task 42 done"),
+            ev(20.0, "main received Data for id 42"),
+            ev(20.0, "main received Data for id 43"),
+        ]
+    );
+}
+
+// the same three fetches, sequential vs join! vs spawn
+#[tokio::test(start_paused = true)]
+async fn sequential_join_spawn_totals() {
+    let start = Instant::now();
+    d07::fetch_data(1, 4).await;
+    d07::fetch_user(1).await;
+    d07::fetch_orders(1).await;
+    assert_eq!(start.elapsed(), secs(12.0)); // 4 + 3 + 5
+
+    let start = Instant::now();
+    tokio::join!(d07::fetch_data(1, 4), d07::fetch_user(1), d07::fetch_orders(1));
+    assert_eq!(start.elapsed(), secs(5.0)); // max(4, 3, 5)
+
+    let start = Instant::now();
+    let handles = (
+        tokio::spawn(d07::fetch_data(1, 4)),
+        tokio::spawn(d07::fetch_user(1)),
+        tokio::spawn(d07::fetch_orders(1)),
+    );
+    let (a, b, c) = tokio::join!(handles.0, handles.1, handles.2);
+    assert_eq!((a.unwrap(), b.unwrap(), c.unwrap().len()), ("Data for id 1".to_string(), "User 1".to_string(), 1));
+    assert_eq!(start.elapsed(), secs(5.0));
+}
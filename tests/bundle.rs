@@ -0,0 +1,87 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use rust_files::bundle::bundle;
+use rust_files::testcase;
+
+fn lib_rs() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs")
+}
+
+// compiles the bundle alone, the way a judge would, and returns the executable
+fn compile(name: &str, code: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rust_files_bundle_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let src = dir.join(format!("{}.rs", name));
+    std::fs::write(&src, code).unwrap();
+    let exe = dir.join(name);
+    let out = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into()))
+        .args(["--edition", "2021", "--crate-name", "bundled", "-o"])
+        .arg(&exe)
+        .arg(&src)
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{} does not compile:\n{}", name, String::from_utf8_lossy(&out.stderr));
+    exe
+}
+
+fn run(exe: &Path, input: &str) -> String {
+    let mut child = Command::new(exe).stdin(Stdio::piped()).stdout(Stdio::piped()).spawn().unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    String::from_utf8(child.wait_with_output().unwrap().stdout).unwrap()
+}
+
+#[test]
+fn cp_solutions_bundle_compile_and_pass_their_samples() {
+    for name in ["04a_cp_input1", "04b_cp_input2", "04c_cp_input3"] {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/bin").join(format!("{}.rs", name));
+        let source = std::fs::read_to_string(&path).unwrap();
+        let b = bundle(&source, &lib_rs()).unwrap();
+        assert!(!b.code.contains("rust_files::"), "{} still refers to the library", name);
+        assert!(b.modules.contains(&"scanner".to_string()));
+        let exe = compile(name, &b.code);
+        for case in testcase::extract(&source) {
+            let actual = run(&exe, &case.input);
+            assert_eq!(testcase::diff_tokens(&case.expected, &actual), None, "{} on {:?}", name, case.input);
+        }
+    }
+}
+
+#[test]
+fn only_used_items_are_inlined() {
+    let solution = r#"
+use rust_files::ds::Fenwick;
+use rust_files::{next, outln};
+use rust_files::output::Output;
+
+fn main() {
+    let n: usize = next!();
+    let mut f = Fenwick::<i64>::new(n);
+    for i in 0..n {
+        f.add(i, next!(i64));
+    }
+    let mut out = Output::stdout();
+    outln!(out, "{}", f.prefix(n));
+}
+"#;
+    let b = bundle(solution, &lib_rs()).unwrap();
+    for kept in ["pub struct Fenwick", "pub struct Output", "macro_rules! next", "macro_rules! outln", "pub struct Scanner"] {
+        assert!(b.code.contains(kept), "missing {}", kept);
+    }
+    for dropped in ["LazySegTree", "Dsu", "SparseTable", "mod graph", "mod stress", "```"] {
+        assert!(!b.code.contains(dropped), "{} should not be bundled", dropped);
+    }
+    assert!(!b.code.contains("use crate::{next, outln}"), "macro imports clash with the inlined macros");
+    assert_eq!(b.modules, ["digits", "ds", "ds::fenwick", "ops", "output", "scanner"]);
+    let exe = compile("fenwick_sum", &b.code);
+    assert_eq!(run(&exe, "3\n1 2 3\n"), "6\n");
+}
+
+#[test]
+fn solution_without_library_is_unchanged() {
+    let solution = "fn main() {\n    println!(\"hi\");\n}\n";
+    let b = bundle(solution, &lib_rs()).unwrap();
+    assert!(b.modules.is_empty());
+    assert!(b.code.ends_with(solution));
+}
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rust_files::cancel::{race_all, with_timeout, CancelToken, TimedOut};
+use tokio::sync::mpsc;
+use tokio::time::{sleep, Duration, Instant};
+
+// everything on the paused clock: a 5s sleep takes no real time and timings are exact
+
+#[tokio::test(start_paused = true)]
+async fn timeout_fires_at_the_deadline() {
+    let start = Instant::now();
+    let r = with_timeout(sleep(Duration::from_secs(5)), Duration::from_secs(2)).await;
+    assert_eq!(r, Err(TimedOut { after: Duration::from_secs(2) }));
+    assert_eq!(start.elapsed(), Duration::from_secs(2));
+    assert_eq!(r.unwrap_err().to_string(), "timed out after 2s");
+}
+
+#[tokio::test(start_paused = true)]
+async fn fast_future_beats_the_timeout() {
+    let start = Instant::now();
+    let r = with_timeout(async { sleep(Duration::from_millis(30)).await; 7 }, Duration::from_secs(1)).await;
+    assert_eq!(r, Ok(7));
+    assert_eq!(start.elapsed(), Duration::from_millis(30));
+}
+
+#[tokio::test(start_paused = true)]
+async fn channel_recv_with_timeout() {
+    // 07e: the message comes after 200ms, we wait 100ms
+    let (tx, mut rx) = mpsc::channel(1);
+    tokio::spawn(async move {
+        sleep(Duration::from_millis(200)).await;
+        let _ = tx.send("Data arrived").await;
+    });
+    assert!(with_timeout(rx.recv(), Duration::from_millis(100)).await.is_err());
+    assert_eq!(with_timeout(rx.recv(), Duration::from_millis(150)).await, Ok(Some("Data arrived")));
+}
+
+async fn background_job(token: CancelToken, rounds: Arc<AtomicUsize>) {
+    while token.run_until_cancelled(sleep(Duration::from_millis(100))).await.is_some() {
+        rounds.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn token_stops_spawned_jobs() {
+    let token = CancelToken::new();
+    let (a, b) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let ja = tokio::spawn(background_job(token.clone(), a.clone()));
+    let jb = tokio::spawn(background_job(token.child(), b.clone()));
+    sleep(Duration::from_millis(350)).await;
+    token.cancel();
+    let start = Instant::now();
+    ja.await.unwrap();
+    jb.await.unwrap();
+    assert_eq!(start.elapsed(), Duration::ZERO); // stopped mid-sleep, not at the end of the round
+    assert_eq!((a.load(Ordering::SeqCst), b.load(Ordering::SeqCst)), (3, 3));
+}
+
+#[tokio::test(start_paused = true)]
+async fn child_cancel_does_not_reach_the_parent() {
+    let parent = CancelToken::new();
+    let child = parent.child();
+    let grandchild = child.child();
+    child.cancel();
+    assert!(child.is_cancelled() && grandchild.is_cancelled());
+    assert!(!parent.is_cancelled());
+    let sibling = parent.child();
+    parent.cancel();
+    assert!(sibling.is_cancelled());
+    assert!(parent.child().is_cancelled()); // born cancelled
+}
+
+#[tokio::test(start_paused = true)]
+async fn cancelled_wakes_every_waiter() {
+    let token = CancelToken::new();
+    let waiters: Vec<_> = (0..5)
+        .map(|_| {
+            let t = token.clone();
+            tokio::spawn(async move { t.cancelled().await })
+        })
+        .collect();
+    sleep(Duration::from_secs(1)).await;
+    token.cancel();
+    for w in waiters {
+        with_timeout(w, Duration::from_millis(1)).await.unwrap().unwrap();
+    }
+    token.cancelled().await; // already cancelled: returns right away
+}
+
+// counts how many of the raced futures were dropped before finishing
+struct DropGuard(Arc<AtomicUsize>);
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn race_all_takes_the_first_success_and_cancels_the_rest() {
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let job = |ms: u64, ok: bool| {
+        let guard = DropGuard(dropped.clone());
+        async move {
+            sleep(Duration::from_millis(ms)).await;
+            std::mem::forget(guard); // finished, not cancelled
+            if ok { Ok(ms) } else { Err(format!("{} failed", ms)) }
+        }
+    };
+    let start = Instant::now();
+    let r = race_all(vec![job(300, true), job(50, false), job(120, true), job(500, true)]).await;
+    assert_eq!(r, Ok(120));
+    assert_eq!(start.elapsed(), Duration::from_millis(120));
+    assert_eq!(dropped.load(Ordering::SeqCst), 2); // the 300ms and 500ms jobs
+}
+
+#[tokio::test(start_paused = true)]
+async fn race_all_collects_all_errors() {
+    let job = |ms: u64| async move {
+        sleep(Duration::from_millis(ms)).await;
+        Err::<(), _>(ms)
+    };
+    let start = Instant::now();
+    assert_eq!(race_all(vec![job(30), job(10), job(20)]).await, Err(vec![30, 10, 20]));
+    assert_eq!(start.elapsed(), Duration::from_millis(30));
+    let none: Vec<std::future::Ready<Result<(), ()>>> = Vec::new();
+    assert_eq!(race_all(none).await, Err(vec![]));
+}
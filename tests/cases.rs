@@ -0,0 +1,40 @@
+use std::io::{Read, Write};
+
+use rust_files::cases;
+use rust_files::outln;
+use rust_files::output::Output;
+use rust_files::scanner::Scanner;
+
+// same shape as the solve() of 04c/04d
+fn sum_line<R: Read, W: Write>(sc: &mut Scanner<R>, out: &mut Output<W>) {
+    let n: usize = sc.next();
+    let a: Vec<i64> = sc.vec(n);
+    outln!(out, "{}", a.iter().sum::<i64>());
+}
+
+fn run(input: &str, multi: bool) -> String {
+    let mut buf = Vec::new();
+    cases::run(&mut Scanner::new(input.as_bytes()), &mut Output::new(&mut buf), multi, sum_line);
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn reads_case_count_first() {
+    assert_eq!(run("3\n1 5\n2 1 2\n0\n", true), "5\n3\n0\n");
+    assert_eq!(run("0\n", true), "");
+}
+
+#[test]
+fn single_case_mode() {
+    assert_eq!(run("2 1 2\n", false), "3\n");
+}
+
+#[test]
+fn closure_state_is_kept_between_cases() {
+    let mut seen = Vec::new();
+    let mut buf = Vec::new();
+    cases::run(&mut Scanner::new("3 a b c".as_bytes()), &mut Output::new(&mut buf), true, |sc, _| {
+        seen.push(sc.next::<String>())
+    });
+    assert_eq!(seen, ["a", "b", "c"]);
+}
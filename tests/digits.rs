@@ -0,0 +1,120 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_files::digits::*;
+
+// random strings that are mostly numbers: optional sign, digits (often long enough to overflow),
+// sometimes junk in the middle
+fn random_number_string(rng: &mut StdRng) -> String {
+    let mut s = String::new();
+    match rng.gen_range(0..6) {
+        0 => s.push('-'),
+        1 => s.push('+'),
+        _ => {}
+    }
+    let len = rng.gen_range(0..45);
+    for _ in 0..len {
+        let c = match rng.gen_range(0..30) {
+            0 => 'x',
+            1 => ' ',
+            2 => '-',
+            _ => (b'0' + rng.gen_range(0..10)) as char,
+        };
+        s.push(c);
+    }
+    s
+}
+
+macro_rules! check_against_std {
+    ($rng:expr, $s:expr, $($t:ty),*) => {$(
+        assert_eq!(checked_parse::<$t>($s), $s.parse::<$t>().ok(), "{:?} as {}", $s, stringify!($t));
+        for base in [2, 8, 16, 36] {
+            assert_eq!(from_base::<$t>($s, base), <$t>::from_str_radix($s, base).ok(), "{:?} base {}", $s, base);
+        }
+        let n: $t = $rng.r#gen();
+        assert_eq!(checked_parse::<$t>(&n.to_string()), Some(n));
+        for base in [2, 7, 10, 16, 36] {
+            assert_eq!(<$t>::from_str_radix(&to_base(n, base), base), Ok(n), "{} base {}", n, base);
+        }
+    )*};
+}
+
+#[test]
+fn checked_parse_and_bases_agree_with_std() {
+    let mut rng = StdRng::seed_from_u64(8);
+    for _ in 0..3_000 {
+        let s = random_number_string(&mut rng);
+        check_against_std!(rng, s.as_str(), i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+    }
+    assert_eq!(checked_parse::<u32>("-0"), None); // like std
+    assert_eq!(checked_parse::<i8>("-128"), Some(i8::MIN));
+    assert_eq!(checked_parse::<i8>("128"), None);
+}
+
+#[test]
+fn saturating_parse_is_atoi() {
+    assert_eq!(saturating_parse::<i32>("42"), 42);
+    assert_eq!(saturating_parse::<i32>("  -042"), -42);
+    assert_eq!(saturating_parse::<i32>("1337c0d3"), 1337);
+    assert_eq!(saturating_parse::<i32>("0-1"), 0);
+    assert_eq!(saturating_parse::<i32>("words and 987"), 0);
+    assert_eq!(saturating_parse::<i32>("-91283472332"), i32::MIN);
+    assert_eq!(saturating_parse::<i32>("+-12"), 0);
+    assert_eq!(saturating_parse::<u8>("300"), 255);
+    assert_eq!(saturating_parse::<u8>("-3"), 0);
+    assert_eq!(saturating_parse::<i128>("99999999999999999999999999999999999999999"), i128::MAX);
+}
+
+#[test]
+fn saturating_parse_clamps_what_checked_parse_rejects() {
+    let mut rng = StdRng::seed_from_u64(9);
+    for _ in 0..20_000 {
+        let sign = ["", "-", "+"][rng.gen_range(0..3)];
+        let digits: String = (0..rng.gen_range(1..25)).map(|_| (b'0' + rng.gen_range(0..10)) as char).collect();
+        let s = format!("{}{}", sign, digits);
+        let expected = s.parse::<i64>().unwrap_or(if sign == "-" { i64::MIN } else { i64::MAX });
+        assert_eq!(saturating_parse::<i64>(&s), expected, "{:?}", s);
+        assert_eq!(saturating_parse::<i64>(&format!("  {}abc", s)), expected);
+    }
+}
+
+#[test]
+fn digit_iteration() {
+    assert_eq!(digits(9051u32), vec![9, 0, 5, 1]);
+    assert_eq!(digits(0i64), vec![0]);
+    assert_eq!(digits(-120i32), vec![1, 2, 0]);
+    assert_eq!(digits_rev(i8::MIN).collect::<Vec<_>>(), vec![8, 2, 1]);
+    assert_eq!(digits_rev(u128::MAX).count(), 39);
+    assert_eq!(digits_rev(987u16).map(u32::from).sum::<u32>(), 24);
+}
+
+#[test]
+fn reverse_detects_overflow() {
+    assert_eq!(reverse_digits(123i32), Some(321));
+    assert_eq!(reverse_digits(-123i32), Some(-321));
+    assert_eq!(reverse_digits(120i32), Some(21));
+    assert_eq!(reverse_digits(0i32), Some(0));
+    assert_eq!(reverse_digits(1534236469i32), None);
+    assert_eq!(reverse_digits(i32::MIN), None);
+    assert_eq!(reverse_digits(1534236469i64), Some(9646324351));
+    assert_eq!(reverse_digits(250u8), Some(52));
+    assert_eq!(reverse_digits(199u8), None);
+
+    // against a string reversal in i128
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..20_000 {
+        let x: i32 = rng.r#gen();
+        let rev: String = x.unsigned_abs().to_string().chars().rev().collect();
+        let wide = rev.parse::<i128>().unwrap() * if x < 0 { -1 } else { 1 };
+        assert_eq!(reverse_digits(x), i32::try_from(wide).ok(), "{}", x);
+    }
+}
+
+#[test]
+fn base_conversion() {
+    assert_eq!(to_base(255u8, 16), "ff");
+    assert_eq!(to_base(-10i32, 2), "-1010");
+    assert_eq!(to_base(0u64, 36), "0");
+    assert_eq!(to_base(i64::MIN, 16), "-8000000000000000");
+    assert_eq!(from_base::<i64>("-zz", 36), Some(-1295));
+    assert_eq!(from_base::<u8>("100000000", 2), None);
+}
@@ -0,0 +1,204 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_files::ds::*;
+use rust_files::ops::{Max, Min, Sum, Xor};
+
+fn random_range(rng: &mut StdRng, n: usize) -> (usize, usize) {
+    let l = rng.gen_range(0..=n);
+    let r = rng.gen_range(l..=n);
+    (l, r)
+}
+
+#[test]
+fn fenwick_matches_naive() {
+    let mut rng = StdRng::seed_from_u64(21);
+    for n in 0..30 {
+        let mut a: Vec<i64> = (0..n).map(|_| rng.gen_range(-100..100)).collect();
+        let mut f = Fenwick::<i64>::from_slice(&a);
+        assert_eq!(f.len(), n);
+        for _ in 0..200 {
+            if n > 0 && rng.r#gen() {
+                let (i, v) = (rng.gen_range(0..n), rng.gen_range(-100..100));
+                a[i] += v;
+                f.add(i, v);
+            } else {
+                let (l, r) = random_range(&mut rng, n);
+                assert_eq!(f.range(l, r), a[l..r].iter().sum::<i64>());
+                assert_eq!(f.prefix(r), a[..r].iter().sum::<i64>());
+            }
+        }
+    }
+}
+
+#[test]
+fn xor_fenwick() {
+    let mut f = Fenwick::<u32, Xor>::new(4);
+    f.add(0, 0b101);
+    f.add(2, 0b110);
+    f.add(3, 0b011);
+    assert_eq!(f.prefix(3), 0b011);
+    assert_eq!(f.range(1, 4), 0b101);
+}
+
+#[test]
+fn range_fenwick_matches_naive() {
+    let mut rng = StdRng::seed_from_u64(22);
+    for n in 0..30 {
+        let mut a = vec![0i64; n];
+        let mut f = RangeFenwick::<i64>::new(n);
+        assert_eq!(f.len(), n);
+        for _ in 0..200 {
+            let (l, r) = random_range(&mut rng, n);
+            let v = rng.gen_range(-100..100);
+            a[l..r].iter_mut().for_each(|x| *x += v);
+            f.range_add(l, r, v);
+            for (i, &x) in a.iter().enumerate() {
+                assert_eq!(f.get(i), x);
+            }
+        }
+    }
+}
+
+#[test]
+fn plain_segtree_min_with_point_set() {
+    let mut st = SegTree::<i32, Min>::from_slice(&[5, 4, 1, 1, 3]);
+    assert_eq!(st.query(0, 2), 4);
+    assert_eq!(st.query(0, 5), 1);
+    assert_eq!(st.query(3, 3), i32::MAX); // empty range is the identity
+    st.set(2, 9);
+    st.set(3, 9);
+    assert_eq!(st.query(0, 5), 3);
+    assert_eq!(st.get(2), 9);
+}
+
+#[test]
+fn lazy_add_sum_matches_naive() {
+    let mut rng = StdRng::seed_from_u64(23);
+    for n in 0..25 {
+        let mut a: Vec<i64> = (0..n).map(|_| rng.gen_range(-100..100)).collect();
+        let mut st = LazySegTree::<i64, Sum, AddSum>::from_slice(&a);
+        for _ in 0..300 {
+            let (l, r) = random_range(&mut rng, n);
+            match rng.gen_range(0..3) {
+                0 => {
+                    let v = rng.gen_range(-100..100);
+                    a[l..r].iter_mut().for_each(|x| *x += v);
+                    st.apply(l, r, v);
+                }
+                1 if n > 0 => {
+                    let (i, v) = (rng.gen_range(0..n), rng.gen_range(-100..100));
+                    a[i] = v;
+                    st.set(i, v);
+                }
+                _ => assert_eq!(st.query(l, r), a[l..r].iter().sum::<i64>()),
+            }
+        }
+    }
+}
+
+#[test]
+fn lazy_add_min_max_matches_naive() {
+    let mut rng = StdRng::seed_from_u64(24);
+    for n in 0..25 {
+        let mut a: Vec<i64> = (0..n).map(|_| rng.gen_range(-100..100)).collect();
+        let mut mn = LazySegTree::<i64, Min, AddMinMax>::from_slice(&a);
+        let mut mx = LazySegTree::<i64, Max, AddMinMax>::from_slice(&a);
+        for _ in 0..300 {
+            let (l, r) = random_range(&mut rng, n);
+            if rng.r#gen() {
+                let v = rng.gen_range(-100..100);
+                a[l..r].iter_mut().for_each(|x| *x += v);
+                mn.apply(l, r, v);
+                mx.apply(l, r, v);
+            } else {
+                assert_eq!(mn.query(l, r), a[l..r].iter().copied().min().unwrap_or(i64::MAX));
+                assert_eq!(mx.query(l, r), a[l..r].iter().copied().max().unwrap_or(i64::MIN));
+            }
+        }
+    }
+}
+
+#[test]
+fn add_min_max_on_a_fresh_tree() {
+    // every leaf starts at the identity, which range adds leave alone
+    let mut mn = LazySegTree::<i64, Min, AddMinMax>::new(5);
+    mn.apply(0, 5, 3);
+    mn.apply(1, 3, -2);
+    assert_eq!((mn.query(0, 5), mn.get(1)), (i64::MAX, i64::MAX));
+    mn.set(4, 7);
+    mn.apply(2, 5, 1);
+    assert_eq!((mn.query(0, 5), mn.get(3)), (8, i64::MAX));
+
+    let mut mx = LazySegTree::<i64, Max, AddMinMax>::new(4);
+    mx.apply(0, 4, -5);
+    assert_eq!((mx.query(0, 4), mx.get(2)), (i64::MIN, i64::MIN));
+    mx.set(0, 1);
+    mx.apply(0, 2, 4);
+    assert_eq!((mx.query(0, 4), mx.get(1)), (5, i64::MIN));
+}
+
+#[test]
+fn add_min_max_near_the_bounds_matches_naive() {
+    // values a few thousand away from the identity, some leaves never set
+    let mut rng = StdRng::seed_from_u64(26);
+    for n in 0..25 {
+        let mut lo = vec![i64::MAX; n];
+        let mut hi = vec![i64::MIN; n];
+        let mut mn = LazySegTree::<i64, Min, AddMinMax>::new(n);
+        let mut mx = LazySegTree::<i64, Max, AddMinMax>::new(n);
+        for _ in 0..300 {
+            let (l, r) = random_range(&mut rng, n);
+            match rng.gen_range(0..3) {
+                0 if n > 0 => {
+                    let (i, d) = (rng.gen_range(0..n), rng.gen_range(100_000..200_000));
+                    (lo[i], hi[i]) = (i64::MAX - d, i64::MIN + d);
+                    mn.set(i, lo[i]);
+                    mx.set(i, hi[i]);
+                }
+                1 => {
+                    let v = rng.gen_range(-300..300);
+                    lo[l..r].iter_mut().filter(|x| **x != i64::MAX).for_each(|x| *x += v);
+                    hi[l..r].iter_mut().filter(|x| **x != i64::MIN).for_each(|x| *x += v);
+                    mn.apply(l, r, v);
+                    mx.apply(l, r, v);
+                }
+                _ => {
+                    assert_eq!(mn.query(l, r), lo[l..r].iter().copied().min().unwrap_or(i64::MAX));
+                    assert_eq!(mx.query(l, r), hi[l..r].iter().copied().max().unwrap_or(i64::MIN));
+                }
+            }
+        }
+        for i in 0..n {
+            assert_eq!((mn.get(i), mx.get(i)), (lo[i], hi[i]));
+        }
+    }
+}
+
+#[test]
+fn lazy_assign_max_matches_naive() {
+    let mut rng = StdRng::seed_from_u64(25);
+    for n in 0..25 {
+        let mut a: Vec<i32> = (0..n).map(|_| rng.gen_range(-100..100)).collect();
+        let mut st = LazySegTree::<i32, Max, Assign>::from_slice(&a);
+        for _ in 0..300 {
+            let (l, r) = random_range(&mut rng, n);
+            if rng.r#gen() {
+                let v = rng.gen_range(-100..100);
+                a[l..r].iter_mut().for_each(|x| *x = v);
+                st.apply(l, r, Some(v));
+            } else {
+                assert_eq!(st.query(l, r), a[l..r].iter().copied().max().unwrap_or(i32::MIN));
+            }
+        }
+    }
+}
+
+#[test]
+fn new_starts_at_identity() {
+    let mut st = LazySegTree::<u64, Sum, AddSum>::new(6);
+    assert_eq!((st.len(), st.query(0, 6)), (6, 0));
+    st.apply(0, 6, 2);
+    st.apply(2, 4, 1);
+    assert_eq!(st.query(1, 5), 10);
+    assert!(SegTree::<u64, Sum>::new(0).is_empty());
+}
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rust_files::fanout::{fetch_many, fetch_many_with_deadline, FetchError};
+use tokio::time::{sleep, Duration, Instant};
+
+// all on tokio's paused clock: sleeps advance virtual time, so timings are exact and instant
+
+#[tokio::test(start_paused = true)]
+async fn results_keep_input_order() {
+    let delays = vec![50u64, 10, 40, 20, 30];
+    let res = fetch_many(delays.clone(), 5, |ms| async move {
+        sleep(Duration::from_millis(ms)).await;
+        Ok::<_, ()>(ms * 2)
+    })
+    .await;
+    assert_eq!(res, delays.iter().map(|&ms| Ok(ms * 2)).collect::<Vec<_>>());
+}
+
+#[tokio::test(start_paused = true)]
+async fn concurrency_is_bounded() {
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let (r, p) = (running.clone(), peak.clone());
+    let start = Instant::now();
+    let res = fetch_many(0..10, 3, move |i| {
+        let (running, peak) = (r.clone(), p.clone());
+        async move {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            sleep(Duration::from_secs(1)).await;
+            running.fetch_sub(1, Ordering::SeqCst);
+            Ok::<_, ()>(i)
+        }
+    })
+    .await;
+    assert_eq!(peak.load(Ordering::SeqCst), 3);
+    assert_eq!(start.elapsed(), Duration::from_secs(4)); // ceil(10 / 3) rounds of 1s
+    assert_eq!(res.len(), 10);
+}
+
+#[tokio::test(start_paused = true)]
+async fn beats_sequential_awaits() {
+    let start = Instant::now();
+    for ms in [400, 200, 100] {
+        sleep(Duration::from_millis(ms)).await;
+    }
+    assert_eq!(start.elapsed(), Duration::from_millis(700));
+
+    let start = Instant::now();
+    fetch_many([400u64, 200, 100], 3, |ms| async move {
+        sleep(Duration::from_millis(ms)).await;
+        Ok::<_, ()>(())
+    })
+    .await;
+    assert_eq!(start.elapsed(), Duration::from_millis(400));
+}
+
+#[tokio::test]
+async fn errors_and_panics_stay_in_their_slot() {
+    let res = fetch_many(vec![1, 0, 2, 3], 2, |i: u32| async move {
+        match i {
+            0 => Err(format!("bad id {}", i)),
+            3 => panic!("job 3 blew up"),
+            _ => Ok(10 / i),
+        }
+    })
+    .await;
+    assert_eq!(res[0], Ok(10));
+    assert_eq!(res[1], Err(FetchError::Failed("bad id 0".to_string())));
+    assert_eq!(res[2], Ok(5));
+    assert_eq!(res[3], Err(FetchError::Panicked));
+}
+
+#[tokio::test(start_paused = true)]
+async fn deadline_cancels_the_rest() {
+    let finished = Arc::new(AtomicUsize::new(0));
+    let f = finished.clone();
+    let start = Instant::now();
+    // 2 slots, 1s per job: 1-4 are done at 2s, 5 and 6 are halfway when the deadline hits
+    let res = fetch_many_with_deadline(1..=6, 2, Duration::from_millis(2500), move |i| {
+        let finished = f.clone();
+        async move {
+            sleep(Duration::from_secs(1)).await;
+            finished.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, ()>(i)
+        }
+    })
+    .await;
+    assert_eq!(start.elapsed(), Duration::from_millis(2500));
+    assert_eq!(&res[..4], &[Ok(1), Ok(2), Ok(3), Ok(4)]);
+    assert_eq!(&res[4..], &[Err(FetchError::DeadlineExceeded), Err(FetchError::DeadlineExceeded)]);
+    sleep(Duration::from_secs(5)).await;
+    assert_eq!(finished.load(Ordering::SeqCst), 4); // 5 and 6 were aborted, not left running
+}
+
+#[tokio::test(start_paused = true)]
+async fn empty_batch() {
+    let res = fetch_many(Vec::<u32>::new(), 0, |i| async move { Ok::<_, ()>(i) }).await;
+    assert!(res.is_empty());
+}
+
+#[tokio::test(start_paused = true)]
+async fn dropping_the_future_cancels_the_jobs() {
+    let started = Arc::new(AtomicUsize::new(0));
+    let finished = Arc::new(AtomicUsize::new(0));
+    let (s, f) = (started.clone(), finished.clone());
+    let batch = fetch_many(0..100, 4, move |i| {
+        let (started, finished) = (s.clone(), f.clone());
+        async move {
+            started.fetch_add(1, Ordering::SeqCst);
+            sleep(Duration::from_secs(1)).await;
+            finished.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, ()>(i)
+        }
+    });
+    // 2.5 rounds in: 8 jobs done, 4 halfway, the other 88 never spawned
+    assert!(tokio::time::timeout(Duration::from_millis(2500), batch).await.is_err());
+    sleep(Duration::from_secs(10)).await;
+    assert_eq!(started.load(Ordering::SeqCst), 12);
+    assert_eq!(finished.load(Ordering::SeqCst), 8);
+}
@@ -0,0 +1,142 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_files::ds::Dsu;
+use rust_files::graph::Graph;
+use rust_files::scanner::Scanner;
+
+fn directed(n: usize, edges: &[(usize, usize)]) -> Graph {
+    let mut g = Graph::new(n, true);
+    for &(u, v) in edges {
+        g.add_edge(u, v, ());
+    }
+    g
+}
+
+#[test]
+fn dsu_unions_and_groups() {
+    let mut d = Dsu::new(6);
+    assert_eq!(d.count(), 6);
+    assert!(d.union(0, 1));
+    assert!(d.union(2, 3));
+    assert!(d.union(1, 3));
+    assert!(!d.union(0, 2));
+    assert_eq!((d.count(), d.size(2), d.size(4)), (3, 4, 1));
+    assert!(d.same(0, 3) && !d.same(0, 5));
+    assert_eq!(d.groups(), vec![vec![0, 1, 2, 3], vec![4], vec![5]]);
+}
+
+#[test]
+fn dsu_matches_naive_labels() {
+    let mut rng = StdRng::seed_from_u64(31);
+    let n = 50;
+    let mut d = Dsu::new(n);
+    let mut label: Vec<usize> = (0..n).collect();
+    for _ in 0..200 {
+        let (a, b) = (rng.gen_range(0..n), rng.gen_range(0..n));
+        let merged = label[a] != label[b];
+        if merged {
+            let (from, to) = (label[b], label[a]);
+            label.iter_mut().filter(|l| **l == from).for_each(|l| *l = to);
+        }
+        assert_eq!(d.union(a, b), merged);
+        let (x, y) = (rng.gen_range(0..n), rng.gen_range(0..n));
+        assert_eq!(d.same(x, y), label[x] == label[y]);
+    }
+}
+
+#[test]
+fn read_one_and_zero_based() {
+    let one = Graph::read(&mut Scanner::new("4 3\n1 2\n2 3\n1 4\n".as_bytes()), false, true);
+    let zero = Graph::read(&mut Scanner::new("4 3\n0 1\n1 2\n0 3\n".as_bytes()), false, false);
+    assert_eq!((one.len(), one.edge_count(), one.is_directed()), (4, 3, false));
+    for u in 0..4 {
+        assert_eq!(one.adj(u), zero.adj(u));
+    }
+    assert_eq!(one.neighbors(0).collect::<Vec<_>>(), vec![1, 3]);
+    assert_eq!(one.neighbors(1).collect::<Vec<_>>(), vec![0, 2]);
+}
+
+#[test]
+fn bfs_and_dfs() {
+    // 0 - 1 - 2 - 3, 0 - 4 - 3, 5 alone
+    let g = Graph::read(&mut Scanner::new("6 5\n0 1\n1 2\n2 3\n0 4\n4 3\n".as_bytes()), false, false);
+    assert_eq!(g.bfs(0), vec![Some(0), Some(1), Some(2), Some(2), Some(1), None]);
+    assert_eq!(g.dfs(0), vec![0, 1, 2, 3, 4]);
+    assert_eq!(g.dfs(5), vec![5]);
+}
+
+#[test]
+fn long_path_does_not_overflow_the_stack() {
+    let n = 300_000;
+    let g = directed(n, &(0..n - 1).map(|i| (i, i + 1)).collect::<Vec<_>>());
+    assert_eq!(g.dfs(0).len(), n);
+    assert_eq!(g.scc().0, n);
+    assert_eq!(g.bfs(0)[n - 1], Some(n - 1));
+}
+
+#[test]
+fn dijkstra_on_weighted_input() {
+    let input = "5 6\n1 2 7\n1 3 2\n3 2 3\n2 4 1\n3 4 9\n4 1 1\n";
+    let g: Graph<u64> = Graph::read_weighted(&mut Scanner::new(input.as_bytes()), true, true);
+    assert_eq!(g.dijkstra(0), vec![Some(0), Some(5), Some(2), Some(6), None]);
+    assert_eq!(g.dijkstra(3), vec![Some(1), Some(6), Some(3), Some(0), None]);
+}
+
+#[test]
+fn dijkstra_matches_bellman_ford() {
+    let mut rng = StdRng::seed_from_u64(32);
+    for _ in 0..50 {
+        let n = rng.gen_range(1..12);
+        let mut g: Graph<i64> = Graph::new(n, rng.r#gen());
+        let mut edges = Vec::new();
+        for _ in 0..rng.gen_range(0..30) {
+            let (u, v, w) = (rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..20));
+            g.add_edge(u, v, w);
+            edges.push((u, v, w));
+            if !g.is_directed() {
+                edges.push((v, u, w));
+            }
+        }
+        let mut dist = vec![None; n];
+        dist[0] = Some(0i64);
+        for _ in 0..n {
+            for &(u, v, w) in &edges {
+                if let Some(du) = dist[u]
+                    && dist[v].is_none_or(|dv| du + w < dv)
+                {
+                    dist[v] = Some(du + w);
+                }
+            }
+        }
+        assert_eq!(g.dijkstra(0), dist);
+    }
+}
+
+#[test]
+fn topo_sort_and_cycle() {
+    let dag = directed(6, &[(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)]);
+    assert_eq!(dag.topo_sort(), Some(vec![4, 5, 0, 2, 3, 1]));
+    let cyclic = directed(3, &[(0, 1), (1, 2), (2, 0)]);
+    assert_eq!(cyclic.topo_sort(), None);
+}
+
+#[test]
+fn scc_components_in_topological_order() {
+    // {0,1,2} -> {3,4} -> {5}, plus {6} pointing into the first one
+    let g = directed(7, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (4, 5), (6, 0)]);
+    let (count, comp) = g.scc();
+    assert_eq!(count, 4);
+    assert!(comp[0] == comp[1] && comp[1] == comp[2]);
+    assert_eq!(comp[3], comp[4]);
+    assert_eq!(comp.iter().collect::<std::collections::HashSet<_>>().len(), 4);
+    for u in 0..g.len() {
+        for v in g.neighbors(u) {
+            assert!(comp[u] <= comp[v], "edge {} -> {} goes backwards", u, v);
+        }
+    }
+    // reversing keeps the components
+    let (rc, rcomp) = g.reversed().scc();
+    assert_eq!(rc, count);
+    assert_eq!(rcomp[0], rcomp[2]);
+    assert_ne!(rcomp[0], rcomp[3]);
+}
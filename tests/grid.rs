@@ -0,0 +1,87 @@
+use rust_files::grid::{DIRS8, Grid};
+use rust_files::scanner::Scanner;
+
+fn sample() -> Grid<i32> {
+    Grid::from_fn(2, 3, |r, c| (r * 10 + c) as i32) // 0 1 2 / 10 11 12
+}
+
+#[test]
+fn indexing_and_bounds() {
+    let mut g = sample();
+    assert_eq!((g.rows(), g.cols(), g.len()), (2, 3, 6));
+    assert_eq!(g[(1, 2)], 12);
+    assert_eq!(g.get(2, 0), None);
+    assert_eq!(g.get(0, 3), None);
+    g[(0, 0)] = 7;
+    *g.get_mut(1, 0).unwrap() += 1;
+    assert_eq!(g.as_slice(), &[7, 1, 2, 11, 11, 12]);
+    assert!(g.in_bounds(1, 2) && !g.in_bounds(-1, 0) && !g.in_bounds(0, 3));
+}
+
+#[test]
+#[should_panic(expected = "(2, 0) outside a 2x3 grid")]
+fn index_out_of_bounds_panics() {
+    let _ = sample()[(2, 0)];
+}
+
+#[test]
+#[should_panic(expected = "row 1 has 4 cells")]
+fn jagged_rows_are_rejected() {
+    // the test2.rs grid
+    Grid::from_rows(vec![vec!['H', 'i'], vec!['R', 'u', 's', 't'], vec!['!']]);
+}
+
+#[test]
+fn neighbours_stay_inside() {
+    let g = Grid::new(3, 3, 0u8);
+    assert_eq!(g.neighbors4(1, 1).count(), 4);
+    assert_eq!(g.neighbors8(1, 1).count(), 8);
+    assert_eq!(g.neighbors4(0, 0).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+    assert_eq!(g.neighbors8(2, 2).collect::<Vec<_>>(), vec![(1, 2), (2, 1), (1, 1)]);
+    assert_eq!(g.neighbors(0, 2, &DIRS8).count(), 3);
+    assert_eq!(g.step((0, 0), (0, -1)), None);
+    assert_eq!(g.step((0, 0), (2, 2)), Some((2, 2)));
+}
+
+#[test]
+fn rows_columns_and_transpose() {
+    let g = sample();
+    assert_eq!(g.row(1), &[10, 11, 12]);
+    assert_eq!(g.col(1).copied().collect::<Vec<_>>(), vec![1, 11]);
+    assert_eq!(g.iter_rows().map(|r| r.iter().sum::<i32>()).collect::<Vec<_>>(), vec![3, 33]);
+    let t = g.transposed();
+    assert_eq!((t.rows(), t.cols()), (3, 2));
+    assert_eq!(t[(2, 1)], 12);
+    assert_eq!(t.get(0, 2), None);
+    assert_eq!(t.row(0).copied().collect::<Vec<_>>(), vec![0, 10]);
+    assert_eq!(t.col(0), &[0, 1, 2]);
+    let owned = g.transpose();
+    assert_eq!(owned, Grid::from_rows(vec![vec![0, 10], vec![1, 11], vec![2, 12]]));
+    assert_eq!(owned.transpose(), g);
+    assert_eq!(t.to_string(), owned.to_string());
+}
+
+#[test]
+fn read_from_scanner() {
+    let mut sc = Scanner::new("3 4\nS..#\n.#..\n...E\n2 2\n1 -2\n30 4\n".as_bytes());
+    let (n, m): (usize, usize) = sc.next();
+    let map = Grid::read_chars(&mut sc, n);
+    assert_eq!(map.cols(), m);
+    assert_eq!(map.position(|&c| c == 'E'), Some((2, 3)));
+    assert_eq!(map.cells().filter(|(_, c)| **c == '#').map(|(p, _)| p).collect::<Vec<_>>(), vec![(0, 3), (1, 1)]);
+    let (r, c): (usize, usize) = sc.next();
+    let nums: Grid<i64> = Grid::read(&mut sc, r, c);
+    assert_eq!(nums.row(1), &[30, 4]);
+
+    let bytes = Grid::read_bytes(&mut Scanner::new("ab\ncd\n".as_bytes()), 2);
+    assert_eq!(bytes.map(|&b| b as char).transpose().to_string(), "a c\nb d\n");
+}
+
+#[test]
+fn pretty_printing() {
+    let g = Grid::from_rows(vec![vec![1, -20, 3], vec![100, 5, 6]]);
+    assert_eq!(g.to_string(), "  1 -20 3\n100   5 6\n");
+    let map = Grid::from_rows(vec!["#.".chars().collect(), ".#".chars().collect()]);
+    assert_eq!(format!("{:#}", map), "#.\n.#\n");
+    assert_eq!(format!("{:?}", map), "[['#', '.'], ['.', '#']]");
+}
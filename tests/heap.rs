@@ -0,0 +1,114 @@
+use std::cmp::Reverse;
+use std::process::{Command, Stdio};
+use std::io::Write;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_files::heap::{self, DaryHeap, one_based};
+use rust_files::testcase;
+
+#[test]
+fn one_based_index_helpers() {
+    assert_eq!((one_based::parent(5), one_based::left(5), one_based::right(5)), (2, 10, 11));
+    assert_eq!((one_based::depth(1), one_based::depth(7), one_based::depth(8)), (0, 2, 3));
+    assert!(one_based::is_ancestor(1, 13));
+    assert!(one_based::is_ancestor(3, 13)); // 13 -> 6 -> 3
+    assert!(one_based::is_ancestor(6, 6));
+    assert!(!one_based::is_ancestor(2, 13));
+    assert!(!one_based::is_ancestor(13, 3));
+    assert!(one_based::on_left_chain(3, 12) && one_based::on_left_chain(12, 3));
+    assert!(!one_based::on_left_chain(3, 13)); // ancestor, but through a right child
+    assert!(!one_based::on_left_chain(3, 9));
+}
+
+#[test]
+fn d_ary_index_helpers() {
+    assert_eq!(heap::parent(5, 2), 2);
+    assert_eq!(heap::parent(5, 4), 1);
+    assert_eq!(heap::children(1, 3, 100), 4..7);
+    assert_eq!(heap::children(1, 3, 5), 4..5);
+    assert!(heap::children(3, 3, 5).is_empty());
+}
+
+#[test]
+fn heapify_builds_valid_heaps() {
+    let mut rng = StdRng::seed_from_u64(10);
+    for d in 2..=5 {
+        for n in 0..60 {
+            let mut a: Vec<i32> = (0..n).map(|_| rng.gen_range(-20..20)).collect();
+            heap::heapify(&mut a, d);
+            assert!(heap::is_heap(&a, d), "d={} {:?}", d, a);
+            if n > 0 {
+                assert_eq!(a[0], *a.iter().min().unwrap());
+            }
+        }
+    }
+    let mut b: Vec<Reverse<i32>> = [3, 9, 1, 7].into_iter().map(Reverse).collect();
+    heap::heapify(&mut b, 2);
+    assert_eq!(b[0], Reverse(9)); // max-heap through Reverse
+    assert!(!heap::is_heap(&[1, 5, 2, 4, 0], 2));
+}
+
+#[test]
+fn dary_heap_with_decrease_key_sorts_like_naive() {
+    let mut rng = StdRng::seed_from_u64(11);
+    for _ in 0..200 {
+        let n = rng.gen_range(1..50);
+        let mut h: DaryHeap<i64, 3> = DaryHeap::new(n);
+        let mut naive: Vec<Option<i64>> = vec![None; n];
+        for _ in 0..100 {
+            let id = rng.gen_range(0..n);
+            let p = rng.gen_range(0..1000);
+            match rng.gen_range(0..3) {
+                0 => {
+                    let changed = h.push_or_decrease(id, p);
+                    let expect = naive[id].is_none_or(|cur| p < cur);
+                    assert_eq!(changed, expect);
+                    if expect {
+                        naive[id] = Some(p);
+                    }
+                }
+                1 if h.contains(id) => {
+                    let lower = naive[id].unwrap() - rng.gen_range(0..10);
+                    h.decrease_key(id, lower);
+                    naive[id] = Some(lower);
+                }
+                _ => {
+                    let min = naive.iter().flatten().min().copied();
+                    let got = h.pop();
+                    assert_eq!(got.map(|g| g.1), min);
+                    if let Some((id, _)) = got {
+                        naive[id] = None;
+                    }
+                }
+            }
+            assert_eq!(h.len(), naive.iter().flatten().count());
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "already in the heap")]
+fn double_push_panics() {
+    let mut h: DaryHeap<u32, 2> = DaryHeap::new(2);
+    h.push(1, 5);
+    h.push(1, 3);
+}
+
+// the Heapify 1 solution, run against the samples at the bottom of its file
+#[test]
+fn heapify_solution_passes_its_samples() {
+    let cases = testcase::extract(include_str!("../src/bin/04a_cp_input1.rs"));
+    assert_eq!(cases.len(), 2);
+    for case in cases {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_04a_cp_input1"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child.stdin.take().unwrap().write_all(case.input.as_bytes()).unwrap();
+        let out = child.wait_with_output().unwrap();
+        let out = String::from_utf8(out.stdout).unwrap();
+        assert_eq!(testcase::diff_tokens(&case.expected, &out), None, "input:\n{}", case.input);
+    }
+}
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use rust_files::http::{HttpClient, HttpError};
+use serde::Deserialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+// canned answer of the stub server: status, body, and how long to wait before answering
+type Reply = (u16, &'static str, Duration);
+
+fn reply(status: u16, body: &'static str) -> Reply {
+    (status, body, Duration::ZERO)
+}
+
+// local http server answering the n-th request with replies[n] (the last one repeats)
+async fn stub(replies: Vec<Reply>) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut sock, _) = listener.accept().await.unwrap();
+            let n = counter.fetch_add(1, Ordering::SeqCst);
+            let (status, body, delay) = replies[n.min(replies.len() - 1)];
+            tokio::spawn(async move {
+                let mut req = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !req.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match sock.read(&mut buf).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(k) => req.extend_from_slice(&buf[..k]),
+                    }
+                }
+                tokio::time::sleep(delay).await;
+                let resp = format!(
+                    "HTTP/1.1 {} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = sock.write_all(resp.as_bytes()).await;
+            });
+        }
+    });
+    (base, hits)
+}
+
+fn fast_client() -> HttpClient {
+    HttpClient::builder()
+        .timeout(Duration::from_millis(300))
+        .retries(3)
+        .backoff(Duration::from_millis(1), Duration::from_millis(4))
+        .build()
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Item {
+    id: u32,
+    name: String,
+}
+
+#[tokio::test]
+async fn json_body_is_deserialized() {
+    let (base, hits) = stub(vec![reply(200, r#"{"id": 7, "name": "seven", "extra": true}"#)]).await;
+    let item: Item = fast_client().get_json(&format!("{}/items/7", base)).await.unwrap();
+    assert_eq!(item, Item { id: 7, name: "seven".into() });
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn server_errors_are_retried_until_success() {
+    let (base, hits) = stub(vec![reply(503, "busy"), reply(500, "oops"), reply(200, "ok")]).await;
+    assert_eq!(fast_client().get_text(&base).await.unwrap(), "ok");
+    assert_eq!(hits.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn server_errors_give_up_after_the_retries() {
+    let (base, hits) = stub(vec![reply(502, "bad gateway")]).await;
+    let err = fast_client().get_text(&base).await.unwrap_err();
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(502));
+    assert_eq!(hits.load(Ordering::SeqCst), 4); // first try + 3 retries
+}
+
+#[tokio::test]
+async fn client_errors_are_not_retried() {
+    let (base, hits) = stub(vec![reply(404, r#"{"error": "no such user"}"#)]).await;
+    match fast_client().get_text(&format!("{}/users/99", base)).await {
+        Err(HttpError::Status { url, status, body }) => {
+            assert!(url.ends_with("/users/99"));
+            assert_eq!(status.as_u16(), 404);
+            assert!(body.contains("no such user"));
+        }
+        other => panic!("expected a 404, got {:?}", other),
+    }
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn slow_server_times_out() {
+    let (base, _) = stub(vec![(200, "late", Duration::from_secs(2))]).await;
+    let err = fast_client().get_text(&base).await.unwrap_err();
+    assert!(matches!(err, HttpError::Timeout { .. }), "{:?}", err);
+    assert!(!err.is_retryable());
+}
+
+#[tokio::test]
+async fn refused_connection_is_a_connect_error() {
+    let port = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap().port(); // freed again
+    let err = fast_client().get_text(&format!("http://127.0.0.1:{}/", port)).await.unwrap_err();
+    assert!(matches!(err, HttpError::Connect { .. }), "{:?}", err);
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn bad_json_is_a_decode_error() {
+    let (base, _) = stub(vec![reply(200, "<html>not json</html>")]).await;
+    let err = fast_client().get_json::<Item>(&base).await.unwrap_err();
+    assert!(matches!(err, HttpError::Decode { .. }), "{:?}", err);
+    assert!(err.to_string().contains("unexpected json"));
+}
+
+#[test]
+fn backoff_doubles_up_to_the_cap() {
+    let c = HttpClient::builder().backoff(Duration::from_millis(100), Duration::from_millis(700)).build();
+    let delays: Vec<u128> = (0..5).map(|a| c.backoff_delay(a).as_millis()).collect();
+    assert_eq!(delays, vec![100, 200, 400, 700, 700]);
+    assert_eq!(c.backoff_delay(200), Duration::from_millis(700));
+}
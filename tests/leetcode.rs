@@ -0,0 +1,35 @@
+use rust_files::leetcode::{self, Problem};
+
+#[test]
+fn every_registered_problem_passes_its_samples() {
+    for p in leetcode::problems() {
+        for r in p.run() {
+            assert!(r.passed, "problem {} ({}) input {}: expected {}, got {}", p.id, p.name, r.input, r.expected, r.actual);
+        }
+    }
+}
+
+#[test]
+fn problems_are_listed_by_id() {
+    let ids: Vec<u32> = leetcode::problems().iter().map(|p| p.id).collect();
+    let mut sorted = ids.clone();
+    sorted.sort();
+    assert_eq!(ids, sorted);
+    assert!(ids.contains(&8));
+}
+
+#[test]
+fn mismatch_is_reported() {
+    let p = Problem::new(1, "double", |x: i32| x * 2, vec![(2, 4), (3, 7)]);
+    let r = p.run();
+    assert!(r[0].passed);
+    assert!(!r[1].passed);
+    assert_eq!((r[1].input.as_str(), r[1].expected.as_str(), r[1].actual.as_str()), ("3", "7", "6"));
+}
+
+#[test]
+fn tuple_input_for_several_arguments() {
+    let p = Problem::new(2, "add", |(a, b): (i64, i64)| a + b, vec![((1, 2), 3)]);
+    assert_eq!(p.run()[0].input, "(1, 2)");
+    assert!(p.run()[0].passed);
+}
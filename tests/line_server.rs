@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use rust_files::line_server::{serve, Config};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+struct Server {
+    addr: String,
+    stop: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+async fn start(config: Config) -> Server {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let (stop, stopped) = oneshot::channel::<()>();
+    let task = tokio::spawn(serve(listener, config, stopped));
+    Server { addr, stop, task }
+}
+
+struct Client {
+    lines: BufReader<OwnedReadHalf>,
+    w: OwnedWriteHalf,
+}
+
+impl Client {
+    async fn connect(addr: &str) -> Client {
+        let (r, w) = TcpStream::connect(addr).await.unwrap().into_split();
+        Client { lines: BufReader::new(r), w }
+    }
+
+    async fn send(&mut self, line: &str) {
+        self.w.write_all(format!("{}\n", line).as_bytes()).await.unwrap();
+    }
+
+    // next reply without the newline, None once the server closed the connection
+    async fn recv(&mut self) -> Option<String> {
+        let mut s = String::new();
+        let n = tokio::time::timeout(Duration::from_secs(5), self.lines.read_line(&mut s)).await.unwrap().unwrap();
+        (n > 0).then(|| s.trim_end().to_string())
+    }
+
+    async fn ask(&mut self, line: &str) -> String {
+        self.send(line).await;
+        self.recv().await.unwrap()
+    }
+}
+
+#[tokio::test]
+async fn commands() {
+    let server = start(Config::default()).await;
+    let mut c = Client::connect(&server.addr).await;
+    assert_eq!(c.ask("ECHO hello  world").await, "hello  world");
+    assert_eq!(c.ask("echo").await, "");
+    let time = c.ask("TIME").await;
+    assert!(chrono::DateTime::parse_from_rfc3339(&time).is_ok(), "{}", time);
+    c.send("").await; // ignored, no reply
+    assert_eq!(c.ask("COUNT").await, "4");
+    assert_eq!(c.ask("JUMP high").await, "ERR unknown command: JUMP");
+    assert_eq!(c.ask("QUIT").await, "BYE");
+    assert_eq!(c.recv().await, None);
+}
+
+#[tokio::test]
+async fn count_is_shared_by_all_clients() {
+    let server = start(Config::default()).await;
+    let mut a = Client::connect(&server.addr).await;
+    let mut b = Client::connect(&server.addr).await;
+    assert_eq!(a.ask("COUNT").await, "1");
+    assert_eq!(b.ask("ECHO x").await, "x");
+    assert_eq!(b.ask("COUNT").await, "3");
+    assert_eq!(a.ask("COUNT").await, "4");
+}
+
+#[tokio::test]
+async fn connection_limit() {
+    let server = start(Config { max_connections: 1, ..Config::default() }).await;
+    let mut first = Client::connect(&server.addr).await;
+    assert_eq!(first.ask("ECHO in").await, "in");
+    let mut second = Client::connect(&server.addr).await;
+    assert_eq!(second.recv().await.as_deref(), Some("ERR server busy"));
+    assert_eq!(second.recv().await, None);
+
+    assert_eq!(first.ask("QUIT").await, "BYE");
+    assert_eq!(first.recv().await, None);
+    tokio::time::sleep(Duration::from_millis(50)).await; // slot is freed when the task ends
+    let mut third = Client::connect(&server.addr).await;
+    assert_eq!(third.ask("ECHO me too").await, "me too");
+}
+
+#[tokio::test]
+async fn idle_clients_are_dropped() {
+    let server = start(Config { idle_timeout: Duration::from_millis(200), ..Config::default() }).await;
+    let mut c = Client::connect(&server.addr).await;
+    assert_eq!(c.ask("ECHO awake").await, "awake");
+    let start = std::time::Instant::now();
+    assert_eq!(c.recv().await.as_deref(), Some("BYE idle timeout"));
+    assert!(start.elapsed() >= Duration::from_millis(200));
+    assert_eq!(c.recv().await, None);
+}
+
+#[tokio::test]
+async fn long_lines_are_refused() {
+    let server = start(Config { max_line: 16, ..Config::default() }).await;
+    let mut c = Client::connect(&server.addr).await;
+    assert_eq!(c.ask("ECHO short").await, "short");
+    assert_eq!(c.ask(&format!("ECHO {}", "x".repeat(100))).await, "ERR line too long");
+    assert_eq!(c.recv().await, None);
+}
+
+#[tokio::test]
+async fn shutdown_drains_open_connections() {
+    let server = start(Config::default()).await;
+    let mut a = Client::connect(&server.addr).await;
+    let mut b = Client::connect(&server.addr).await;
+    assert_eq!(a.ask("ECHO a").await, "a");
+    assert_eq!(b.ask("ECHO b").await, "b");
+
+    server.stop.send(()).unwrap();
+    assert_eq!(a.recv().await.as_deref(), Some("BYE server shutting down"));
+    assert_eq!(b.recv().await.as_deref(), Some("BYE server shutting down"));
+    assert_eq!(a.recv().await, None);
+    tokio::time::timeout(Duration::from_secs(5), server.task).await.unwrap().unwrap();
+    assert!(TcpStream::connect(&server.addr).await.is_err()); // listener is gone
+}
+
+// the binary: prints its address, serves, and drains on ctrl-c (SIGINT)
+#[cfg(unix)]
+#[tokio::test]
+async fn binary_stops_on_ctrl_c() {
+    use std::process::Stdio;
+    use tokio::process::Command;
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_line_server"))
+        .arg("127.0.0.1:0")
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut out = BufReader::new(child.stdout.take().unwrap()).lines();
+    let first = out.next_line().await.unwrap().unwrap();
+    let addr = first.strip_prefix("listening on ").unwrap().to_string();
+
+    let mut c = Client::connect(&addr).await;
+    assert_eq!(c.ask("ECHO from a process").await, "from a process");
+    let kill = std::process::Command::new("kill").args(["-INT", &child.id().unwrap().to_string()]).status();
+    assert!(kill.unwrap().success());
+    assert_eq!(c.recv().await.as_deref(), Some("BYE server shutting down"));
+    let status = tokio::time::timeout(Duration::from_secs(5), child.wait()).await.unwrap().unwrap();
+    assert!(status.success());
+    assert_eq!(out.next_line().await.unwrap().as_deref(), Some("shutting down, waiting for open connections"));
+}
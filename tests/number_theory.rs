@@ -0,0 +1,113 @@
+use rust_files::number_theory::*;
+
+#[test]
+fn gcd_and_lcm_are_generic() {
+    assert_eq!(gcd(12i64, 18), 6);
+    assert_eq!(gcd(17u32, 5), 1);
+    assert_eq!(gcd(0usize, 7), 7);
+    assert_eq!(gcd(1_000_000_000i64, 1_000_000_000), 1_000_000_000);
+    assert_eq!(lcm(4u64, 6), 12);
+    assert_eq!(lcm(0i32, 5), 0);
+    assert_eq!(lcm(1_000_000_007u64, 998_244_353), 1_000_000_007 * 998_244_353);
+}
+
+#[test]
+fn ext_gcd_gives_bezout_coefficients() {
+    for &(a, b) in &[(240, 46), (46, 240), (-15, 35), (7, 0), (0, 7), (1_000_000_007, 123_456)] {
+        let (g, x, y) = ext_gcd(a, b);
+        assert_eq!(g, gcd(a, b).abs(), "a={} b={}", a, b);
+        assert_eq!(a * x + b * y, g);
+    }
+}
+
+#[test]
+fn mod_inv_exists_only_for_coprime() {
+    assert_eq!(mod_inv(3, 11), Some(4));
+    assert_eq!(mod_inv(-3, 11), Some(7));
+    assert_eq!(mod_inv(6, 9), None);
+    let m = 1_000_000_007;
+    assert_eq!(mod_inv(2, m), Some(500_000_004));
+}
+
+#[test]
+fn mod_pow_matches_naive() {
+    assert_eq!(mod_pow(2, 10, 1_000_000_007), 1024);
+    assert_eq!(mod_pow(2, 0, 7), 1);
+    assert_eq!(mod_pow(5, 3, 1), 0);
+    assert_eq!(mod_pow(3, 1_000_000_006, 1_000_000_007), 1); // fermat
+    assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+}
+
+#[test]
+fn modint_operators() {
+    type Mint = ModInt1e9;
+    let a = Mint::new(1_000_000_006); // -1
+    assert_eq!(a + 2, Mint::new(1));
+    assert_eq!(Mint::new(1) - 2, a);
+    assert_eq!(-a, Mint::new(1));
+    assert_eq!(a * a, Mint::new(1));
+    assert_eq!(Mint::from(-5i64).value(), 1_000_000_002);
+    assert_eq!(Mint::new(10) / Mint::new(4) * 4, Mint::new(10));
+    let mut x = Mint::new(3);
+    x *= 3;
+    x += Mint::new(1);
+    x -= 2;
+    x /= 2;
+    assert_eq!(x.value(), 4);
+    assert_eq!(Mint::new(2).pow(30).value(), (1u64 << 30) % 1_000_000_007);
+    assert_eq!(Mint::new(7).inv() * 7, Mint::new(1));
+    let fact: ModInt998 = (1..=20usize).map(ModInt998::from).product();
+    assert_eq!(fact.value(), (2_432_902_008_176_640_000u64 % 998_244_353));
+    assert_eq!(format!("{}", ModInt998::new(998_244_354)), "1");
+}
+
+#[test]
+#[should_panic(expected = "no inverse")]
+fn modint_inv_of_zero_panics() {
+    let _ = ModInt998::new(0).inv();
+}
+
+#[test]
+fn sieve_matches_trial_division() {
+    let s = Sieve::new(10_000);
+    assert_eq!(&s.primes()[..10], &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    assert_eq!(s.primes().len(), 1229);
+    assert_eq!(s.spf(1), None);
+    assert_eq!(s.spf(91), Some(7));
+    assert!(s.is_prime(9973) && !s.is_prime(9999) && !s.is_prime(1));
+    for x in 2..=s.limit() {
+        assert_eq!(s.factorize(x), factorize(x as u64), "x={}", x);
+        assert_eq!(s.is_prime(x), factorize(x as u64) == vec![(x as u64, 1)]);
+    }
+}
+
+#[test]
+fn factorize_big_numbers() {
+    assert_eq!(factorize(1), vec![]);
+    assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+    assert_eq!(factorize(1_000_000_007), vec![(1_000_000_007, 1)]);
+    assert_eq!(factorize(1_000_036_000_099), vec![(1_000_003, 1), (1_000_033, 1)]);
+    assert_eq!(factorize(u64::MAX), vec![(3, 1), (5, 1), (17, 1), (257, 1), (641, 1), (65_537, 1), (6_700_417, 1)]);
+}
+
+// only a number with no factor below ~2^32 gets p past 2^32, where p * p would overflow, and that
+// is ~2^31 trial divisions (about 20s in debug): cargo test --release -- --ignored
+#[test]
+#[ignore]
+fn factorize_largest_u64_prime() {
+    assert_eq!(factorize(18_446_744_073_709_551_557), vec![(18_446_744_073_709_551_557, 1)]);
+}
+
+#[test]
+fn modint_with_a_modulus_above_2_63() {
+    type Big = ModInt<18_446_744_073_709_551_557>; // largest u64 prime
+    let m = 18_446_744_073_709_551_557u64;
+    let a = Big::new(m - 1);
+    assert_eq!(a + a, Big::new(m - 2)); // the u64 sum overflows
+    assert_eq!(a + Big::new(1), Big::new(0));
+    assert_eq!(Big::new(1) - a, Big::new(2));
+    assert_eq!(Big::from(-1i64), a);
+    assert_eq!(a.inv(), a); // -1 is its own inverse
+    let x = Big::new(123_456_789_123_456_789);
+    assert_eq!(x * x.inv(), Big::new(1));
+}
@@ -0,0 +1,54 @@
+use rust_files::output::Output;
+use rust_files::{out, outln};
+
+// runs `f` against an Output over a Vec and returns what was written after the drop-flush
+fn written(f: impl FnOnce(&mut Output<&mut Vec<u8>>)) -> String {
+    let mut buf = Vec::new();
+    {
+        let mut out = Output::new(&mut buf);
+        f(&mut out);
+    }
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn macros_write_through_the_buffer() {
+    let s = written(|o| {
+        out!(o, "{} ", 1);
+        out!(o, "{}", 2);
+        outln!(o);
+        outln!(o, "ans = {}", 3);
+    });
+    assert_eq!(s, "1 2\nans = 3\n");
+}
+
+#[test]
+fn yes_no_helpers() {
+    let s = written(|o| {
+        o.yes();
+        o.no();
+        o.yes_no(1 + 1 == 2);
+    });
+    assert_eq!(s, "YES\nNO\nYES\n");
+}
+
+#[test]
+fn join_prints_space_separated() {
+    let s = written(|o| {
+        o.join(vec![3, 1, 2]);
+        o.join(Vec::<i32>::new());
+        o.join(["a", "b"].iter());
+    });
+    assert_eq!(s, "3 1 2\n\na b\n");
+}
+
+#[test]
+fn large_output_is_flushed_on_drop() {
+    let s = written(|o| {
+        for i in 0..100_000 {
+            outln!(o, "{}", i);
+        }
+    });
+    assert_eq!(s.lines().count(), 100_000);
+    assert_eq!(s.lines().last(), Some("99999"));
+}
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rust_files::pipeline::{DeadLetter, Pipeline, StageReport};
+use tokio::sync::mpsc;
+use tokio::time::{sleep, Duration, Instant};
+
+#[tokio::test]
+async fn failures_go_to_dead_letters() {
+    let (mut out, report) = Pipeline::from_iter(4, vec!["3", "x", "5", "", "7"])
+        .stage("parse", 2, 4, |s: &str| async move { s.parse::<u32>() })
+        .stage("half", 2, 4, |n: u32| async move {
+            if n % 2 == 1 && n > 5 { Err(format!("{} is too odd", n)) } else { Ok(n * 10) }
+        })
+        .collect()
+        .await;
+    out.sort();
+    assert_eq!(out, vec![30, 50]);
+    assert_eq!(report.stage("parse"), Some(&StageReport { name: "parse", ok: 3, failed: 2 }));
+    assert_eq!(report.stage("half"), Some(&StageReport { name: "half", ok: 2, failed: 1 }));
+    let mut dead = report.dead_letters.clone();
+    dead.sort_by(|a, b| a.item.cmp(&b.item));
+    assert_eq!(dead.len(), 3);
+    assert_eq!(dead[0].item, "\"\"");
+    assert_eq!(dead[1].item, "\"x\"");
+    assert_eq!(dead[2], DeadLetter { stage: "half", item: "7".into(), error: "7 is too odd".into() });
+}
+
+#[tokio::test(start_paused = true)]
+async fn slow_sink_holds_back_the_source() {
+    let pulled = Arc::new(AtomicUsize::new(0));
+    let done = Arc::new(AtomicUsize::new(0));
+    let max_gap = Arc::new(AtomicUsize::new(0));
+    let p = pulled.clone();
+    let source = (0..100).inspect(move |_| {
+        p.fetch_add(1, Ordering::SeqCst);
+    });
+    let (pulled2, done2, gap) = (pulled.clone(), done.clone(), max_gap.clone());
+    let report = Pipeline::from_iter(2, source)
+        .stage("double", 1, 2, |n: i32| async move { Ok::<_, String>(n * 2) })
+        .sink("slow", 1, move |_n: i32| {
+            let (pulled, done, gap) = (pulled2.clone(), done2.clone(), gap.clone());
+            async move {
+                sleep(Duration::from_millis(10)).await;
+                let d = done.fetch_add(1, Ordering::SeqCst) + 1;
+                gap.fetch_max(pulled.load(Ordering::SeqCst) - d, Ordering::SeqCst);
+                Ok::<_, String>(())
+            }
+        })
+        .await;
+    assert_eq!(report.stage("slow").unwrap().ok, 100);
+    // at most: 1 held by the source + 2 queued + 1 in "double" + 2 queued + 1 in the sink
+    assert!(max_gap.load(Ordering::SeqCst) <= 7, "{} items in flight", max_gap.load(Ordering::SeqCst));
+}
+
+#[tokio::test(start_paused = true)]
+async fn workers_run_in_parallel() {
+    let start = Instant::now();
+    let (out, _) = Pipeline::from_iter(8, 0..8)
+        .stage("slow", 4, 8, |n: u32| async move {
+            sleep(Duration::from_secs(1)).await;
+            Ok::<_, String>(n)
+        })
+        .collect()
+        .await;
+    assert_eq!(out.len(), 8);
+    assert_eq!(start.elapsed(), Duration::from_secs(2)); // 8 items / 4 workers
+}
+
+#[tokio::test]
+async fn ends_when_all_producers_are_gone() {
+    let (tx, rx) = mpsc::channel(4);
+    for p in 0..3 {
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            for i in 0..5 {
+                tx.send(p * 100 + i).await.unwrap();
+            }
+        });
+    }
+    drop(tx);
+    let (mut out, report) = Pipeline::from_receiver(rx)
+        .stage("inc", 3, 4, |n: i32| async move { Ok::<_, String>(n + 1) })
+        .collect()
+        .await;
+    out.sort();
+    let mut want: Vec<i32> = (0..3).flat_map(|p| (0..5).map(move |i| p * 100 + i + 1)).collect();
+    want.sort();
+    assert_eq!(out, want);
+    assert!(report.dead_letters.is_empty());
+}
+
+#[tokio::test]
+async fn dead_letters_can_be_watched_live() {
+    let mut p = Pipeline::from_iter(4, vec![1, -1, 2, -2]).stage("check", 1, 4, |n: i32| async move {
+        if n < 0 { Err("negative") } else { Ok(n) }
+    });
+    let mut dead = p.dead_letters();
+    let (out, report) = p.collect().await;
+    assert_eq!(out, vec![1, 2]); // one worker keeps the order
+    assert!(report.dead_letters.is_empty());
+    let mut seen = Vec::new();
+    while let Some(d) = dead.recv().await {
+        seen.push(d.to_string());
+    }
+    assert_eq!(seen, vec!["[check] -1: negative", "[check] -2: negative"]);
+}
+
+#[tokio::test]
+#[should_panic(expected = "stage blew up")]
+async fn panics_are_not_swallowed() {
+    let _ = Pipeline::from_iter(4, 0..3)
+        .stage("boom", 1, 4, |n: i32| async move {
+            if n == 1 {
+                panic!("stage blew up");
+            }
+            Ok::<_, String>(n)
+        })
+        .collect()
+        .await;
+}
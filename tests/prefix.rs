@@ -0,0 +1,68 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_files::number_theory::gcd;
+use rust_files::ops::*;
+use rust_files::prefix::{PrefixAgg, SparseTable};
+
+fn random_vec(rng: &mut StdRng, n: usize) -> Vec<i64> {
+    (0..n).map(|_| rng.gen_range(-1000..1000)).collect()
+}
+
+#[test]
+fn prefix_and_suffix_folds() {
+    let a = [5usize, 4, 1, 1, 1, 1, 3]; // second sample of 04b
+    let min = PrefixAgg::<usize, Min>::new(&a);
+    assert_eq!(min.suffixes(), &[1, 1, 1, 1, 1, 1, 3, usize::MAX]);
+    assert_eq!(min.prefixes(), &[usize::MAX, 5, 4, 1, 1, 1, 1, 1]);
+    assert_eq!(min.except(2), 1);
+    let sum = PrefixAgg::<usize, Sum>::new(&a);
+    assert_eq!((sum.prefix(7), sum.suffix(5), sum.len()), (16, 4, 7));
+    assert_eq!(sum.except(0), 11);
+}
+
+#[test]
+fn invertible_ranges_match_naive() {
+    let mut rng = StdRng::seed_from_u64(12);
+    for n in 0..40 {
+        let a = random_vec(&mut rng, n);
+        let sum = PrefixAgg::<i64, Sum>::new(&a);
+        let bits: Vec<u32> = a.iter().map(|&x| x as u32).collect();
+        let xor = PrefixAgg::<u32, Xor>::new(&bits);
+        for l in 0..=n {
+            for r in l..=n {
+                assert_eq!(sum.range(l, r), a[l..r].iter().sum::<i64>());
+                assert_eq!(xor.range(l, r), bits[l..r].iter().fold(0, |x, y| x ^ y));
+            }
+        }
+    }
+}
+
+#[test]
+fn sparse_tables_match_naive() {
+    let mut rng = StdRng::seed_from_u64(13);
+    for n in 0..40 {
+        let a = random_vec(&mut rng, n);
+        let pos: Vec<u64> = a.iter().map(|x| x.unsigned_abs()).collect();
+        let mn = SparseTable::<i64, Min>::new(&a);
+        let mx = SparseTable::<i64, Max>::new(&a);
+        let g = SparseTable::<u64, Gcd>::new(&pos);
+        let and = SparseTable::<i64, And>::new(&a);
+        let or = SparseTable::<u64, Or>::new(&pos);
+        for l in 0..n {
+            for r in l + 1..=n {
+                assert_eq!(mn.query(l, r), *a[l..r].iter().min().unwrap());
+                assert_eq!(mx.query(l, r), *a[l..r].iter().max().unwrap());
+                assert_eq!(g.query(l, r), pos[l..r].iter().fold(0, |x, &y| gcd(x, y)));
+                assert_eq!(and.query(l, r), a[l..r].iter().fold(-1, |x, y| x & y));
+                assert_eq!(or.query(l, r), pos[l..r].iter().fold(0, |x, y| x | y));
+            }
+        }
+    }
+    assert_eq!(SparseTable::<i64, Min>::new(&[4, 2]).query(1, 1), i64::MAX);
+}
+
+#[test]
+#[should_panic(expected = "bad range")]
+fn out_of_bounds_range_panics() {
+    PrefixAgg::<i32, Sum>::new(&[1, 2]).range(1, 3);
+}
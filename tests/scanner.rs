@@ -0,0 +1,94 @@
+use rust_files::scanner::{ScanError, Scanner};
+
+fn scanner(input: &str) -> Scanner<&[u8]> {
+    Scanner::new(input.as_bytes())
+}
+
+#[test]
+fn reads_integers_of_every_width() {
+    let mut sc = scanner("-128 255 -2147483648 18446744073709551615 +7\n");
+    assert_eq!(sc.next::<i8>(), -128);
+    assert_eq!(sc.next::<u8>(), 255);
+    assert_eq!(sc.next::<i32>(), i32::MIN);
+    assert_eq!(sc.next::<u64>(), u64::MAX);
+    assert_eq!(sc.next::<i64>(), 7);
+}
+
+#[test]
+fn reads_floats_strings_chars_and_tuples() {
+    let mut sc = scanner("3.5 hello x\n1 -2 abc\n");
+    assert_eq!(sc.next::<f64>(), 3.5);
+    assert_eq!(sc.next::<String>(), "hello");
+    assert_eq!(sc.next::<char>(), 'x');
+    let (a, b, s): (u32, i64, String) = sc.next();
+    assert_eq!((a, b, s.as_str()), (1, -2, "abc"));
+}
+
+#[test]
+fn reads_vec_and_grid() {
+    let mut sc = scanner("5\n1 4 3 2 5\n2\n#.\n.#\n");
+    let n: usize = sc.next();
+    assert_eq!(sc.vec::<usize>(n), vec![1, 4, 3, 2, 5]);
+    let rows: usize = sc.next();
+    assert_eq!(sc.grid(rows), vec![vec!['#', '.'], vec!['.', '#']]);
+}
+
+#[test]
+fn line_skips_blank_lines_and_stops_at_newline() {
+    let mut sc = scanner("\n\n1 2 3\n4 5\n");
+    assert_eq!(sc.line::<i32>(), vec![1, 2, 3]);
+    assert_eq!(sc.line::<i32>(), vec![4, 5]);
+    assert!(sc.token().is_none());
+}
+
+#[test]
+fn token_longer_than_buffer() {
+    let long = "9".repeat(200_000);
+    let input = format!("{} 42", long);
+    let mut sc = scanner(&input);
+    assert_eq!(sc.bytes().len(), long.len());
+    assert_eq!(sc.next::<u8>(), 42);
+}
+
+#[test]
+fn last_token_without_trailing_newline() {
+    let mut sc = scanner("  10   20");
+    assert_eq!(sc.vec::<i32>(2), vec![10, 20]);
+    assert!(sc.token().is_none());
+}
+
+#[test]
+#[should_panic(expected = "line 1, column 1: cannot parse \"256\" as u8")]
+fn overflow_panics() {
+    scanner("256").next::<u8>();
+}
+
+#[test]
+fn try_next_reports_eof() {
+    let mut sc = scanner("1\n");
+    assert_eq!(sc.try_next::<i32>(), Ok(1));
+    assert_eq!(sc.try_next::<i32>(), Err(ScanError::UnexpectedEof));
+    assert_eq!(scanner("1 2").try_vec::<i32>(3), Err(ScanError::UnexpectedEof));
+}
+
+#[test]
+fn parse_failure_has_token_line_and_column() {
+    let mut sc = scanner("2\n10 20\n  30 4x0\n");
+    assert_eq!(sc.try_vec::<i64>(4), Ok(vec![2, 10, 20, 30]));
+    assert_eq!(
+        sc.try_next::<i64>(),
+        Err(ScanError::ParseFailure { token: "4x0".to_string(), expected: "i64", line: 3, column: 6 })
+    );
+}
+
+#[test]
+fn overflow_is_a_parse_failure() {
+    let err = scanner("\n\n300").try_next::<u8>().unwrap_err();
+    assert_eq!(err.to_string(), "line 3, column 1: cannot parse \"300\" as u8");
+}
+
+#[test]
+fn try_line_stops_at_bad_token() {
+    let mut sc = scanner("1 2 x\n");
+    assert!(matches!(sc.try_line::<u32>(), Err(ScanError::ParseFailure { column: 5, .. })));
+}
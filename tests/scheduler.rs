@@ -0,0 +1,180 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use chrono::{NaiveDate, NaiveTime};
+use rust_files::scheduler::{until_next, MissedTick, Schedule, Scheduler, SchedulerError};
+use tokio::time::{sleep, Duration, Instant};
+
+// all on tokio's paused clock, so times are exact
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+type Log = Arc<Mutex<Vec<u64>>>;
+
+// a job that records when (ms since `start`) each run began; run n takes run_ms(n)
+fn recorder(
+    start: Instant,
+    log: &Log,
+    run_ms: impl Fn(usize) -> u64 + Send + Sync + 'static,
+) -> impl Fn() -> tokio::time::Sleep + Send + Sync + 'static {
+    let log = log.clone();
+    move || {
+        let mut log = log.lock().unwrap();
+        let n = log.len();
+        log.push(start.elapsed().as_millis() as u64);
+        sleep(ms(run_ms(n)))
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn one_shot_runs_once_and_leaves_the_list() {
+    let sched = Scheduler::new();
+    let log = Log::default();
+    sched.add("once", Schedule::once(ms(300)), recorder(Instant::now(), &log, |_| 0)).unwrap();
+    assert_eq!(sched.list()[0].name, "once");
+    sleep(ms(1000)).await;
+    assert_eq!(*log.lock().unwrap(), vec![300]);
+    assert!(sched.list().is_empty());
+}
+
+#[tokio::test(start_paused = true)]
+async fn interval_runs_on_the_grid() {
+    let sched = Scheduler::new();
+    let log = Log::default();
+    sched.add("every", Schedule::every(ms(100)), recorder(Instant::now(), &log, |_| 10)).unwrap();
+    sleep(ms(450)).await;
+    assert_eq!(*log.lock().unwrap(), vec![0, 100, 200, 300, 400]);
+    assert_eq!(sched.list()[0].runs, 5);
+}
+
+// first run takes 250ms on a 100ms grid, the others 10ms
+async fn starts_with(missed: MissedTick) -> Vec<u64> {
+    let sched = Scheduler::new();
+    let log = Log::default();
+    let job = recorder(Instant::now(), &log, |n| if n == 0 { 250 } else { 10 });
+    sched.add("slow start", Schedule::every(ms(100)).on_missed(missed), job).unwrap();
+    sleep(ms(520)).await;
+    sched.shutdown().await;
+    log.lock().unwrap().clone()
+}
+
+#[tokio::test(start_paused = true)]
+async fn missed_tick_policies() {
+    // the ticks due at 100 and 200 are missed while the first run is still going
+    assert_eq!(starts_with(MissedTick::Burst).await, vec![0, 250, 260, 300, 400, 500]);
+    assert_eq!(starts_with(MissedTick::Delay).await, vec![0, 250, 350, 450]);
+    assert_eq!(starts_with(MissedTick::Skip).await, vec![0, 250, 300, 400, 500]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn pause_skips_ticks_until_resume() {
+    let sched = Scheduler::new();
+    let log = Log::default();
+    sched.add("job", Schedule::every(ms(100)), recorder(Instant::now(), &log, |_| 0)).unwrap();
+    sleep(ms(150)).await;
+    assert!(sched.pause("job"));
+    assert!(sched.list()[0].paused);
+    sleep(ms(300)).await;
+    assert!(sched.resume("job"));
+    sleep(ms(200)).await;
+    assert_eq!(*log.lock().unwrap(), vec![0, 100, 500, 600]);
+    assert!(!sched.pause("nope"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn paused_one_shot_waits_for_resume() {
+    let sched = Scheduler::new();
+    let log = Log::default();
+    sched.add("once", Schedule::once(ms(100)), recorder(Instant::now(), &log, |_| 0)).unwrap();
+    sched.pause("once");
+    sleep(ms(400)).await;
+    assert!(log.lock().unwrap().is_empty());
+    sched.resume("once");
+    sleep(ms(1)).await;
+    assert_eq!(*log.lock().unwrap(), vec![400]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn cancel_and_list() {
+    let sched = Scheduler::new();
+    let runs = Arc::new(AtomicUsize::new(0));
+    for name in ["b", "a", "c"] {
+        let runs = runs.clone();
+        sched
+            .add(name, Schedule::every(ms(100)), move || {
+                runs.fetch_add(1, Ordering::SeqCst);
+                async {}
+            })
+            .unwrap();
+    }
+    let dup = sched.add("a", Schedule::once(ms(1)), || async {});
+    assert_eq!(dup, Err(SchedulerError::Duplicate("a".to_string())));
+    let names: Vec<_> = sched.list().into_iter().map(|j| j.name).collect();
+    assert_eq!(names, vec!["a", "b", "c"]);
+
+    sleep(ms(50)).await; // one run each so far
+    assert!(sched.cancel("b"));
+    assert!(!sched.cancel("b"));
+    sleep(ms(200)).await; // a and c run at 100 and 200
+    assert_eq!(runs.load(Ordering::SeqCst), 3 + 2 * 2);
+    assert_eq!(sched.list().len(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn shutdown_waits_for_runs_in_progress() {
+    let sched = Scheduler::new();
+    let finished = Arc::new(AtomicUsize::new(0));
+    let f = finished.clone();
+    sched
+        .add("long", Schedule::once(ms(100)), move || {
+            let f = f.clone();
+            async move {
+                sleep(ms(1000)).await;
+                f.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .unwrap();
+    sched.add("later", Schedule::once(ms(5000)), || async { panic!("should never run") }).unwrap();
+    sleep(ms(500)).await;
+    assert!(sched.list().iter().any(|j| j.name == "long" && j.running));
+
+    let start = Instant::now();
+    sched.shutdown().await;
+    assert_eq!(start.elapsed(), ms(600)); // the run started at 100ms ends at 1100ms
+    assert_eq!(finished.load(Ordering::SeqCst), 1);
+    assert!(sched.list().is_empty());
+    assert_eq!(sched.add("x", Schedule::once(ms(1)), || async {}), Err(SchedulerError::ShutDown));
+}
+
+#[tokio::test(start_paused = true)]
+async fn a_panicking_run_keeps_the_schedule() {
+    let sched = Scheduler::new();
+    let runs = Arc::new(AtomicUsize::new(0));
+    let r = runs.clone();
+    sched
+        .add("flaky", Schedule::every(ms(100)), move || {
+            let n = r.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if n == 1 {
+                    panic!("second run fails");
+                }
+            }
+        })
+        .unwrap();
+    sleep(ms(350)).await;
+    assert_eq!(runs.load(Ordering::SeqCst), 4);
+    assert_eq!(sched.list()[0].runs, 4);
+}
+
+#[test]
+fn next_daily_run() {
+    let day = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+    let at = NaiveTime::from_hms_opt(6, 30, 0).unwrap();
+    let early = day.and_hms_opt(6, 0, 0).unwrap();
+    let late = day.and_hms_opt(18, 0, 0).unwrap();
+    assert_eq!(until_next(at, early), Duration::from_secs(30 * 60));
+    assert_eq!(until_next(at, late), Duration::from_secs(12 * 3600 + 30 * 60));
+    assert_eq!(until_next(at, day.and_time(at)), Duration::from_secs(24 * 3600)); // just ran: tomorrow
+}
@@ -0,0 +1,77 @@
+use rand::Rng;
+use rust_files::stress::{Shrink, Stress};
+
+fn gen_vec(rng: &mut rand::rngs::StdRng) -> Vec<u32> {
+    let n = rng.gen_range(0..20);
+    (0..n).map(|_| rng.gen_range(0..100)).collect()
+}
+
+#[test]
+fn equal_solutions_pass_every_case() {
+    let res = Stress::new().cases(300).run(gen_vec, |v| v.iter().max().copied(), |v| {
+        let mut w = v.clone();
+        w.sort();
+        w.last().copied()
+    });
+    assert_eq!(res.unwrap(), 300);
+}
+
+#[test]
+fn failure_is_shrunk_to_a_minimal_input() {
+    // candidate is wrong as soon as an element reaches 50
+    let f = Stress::new()
+        .cases(300)
+        .seed(1)
+        .run(gen_vec, |v| v.iter().sum::<u32>(), |v| v.iter().filter(|&&x| x < 50).sum::<u32>())
+        .unwrap_err();
+    assert_eq!(f.input, vec![50]);
+    assert_eq!((f.expected, f.actual), (50, 0));
+    assert_eq!(f.seed, 1 + f.case as u64);
+}
+
+#[test]
+fn failing_seed_regenerates_original_input() {
+    use rand::SeedableRng;
+    let f = Stress::new()
+        .seed(99)
+        .run(gen_vec, |v| v.len(), |v| v.len().min(10))
+        .unwrap_err();
+    assert_eq!(gen_vec(&mut rand::rngs::StdRng::seed_from_u64(f.seed)), f.original);
+    assert_eq!(f.input, vec![0; 11]);
+}
+
+#[test]
+#[should_panic(expected = "shrunk input:   (10, 0)")]
+fn check_panics_with_report() {
+    Stress::new().check(|rng| (rng.gen_range(0..1000), rng.gen_range(0..1000)), |&(a, _)| a < 10, |_| true);
+}
+
+#[test]
+fn integers_shrink_toward_zero() {
+    assert_eq!(100u32.shrink().collect::<Vec<_>>(), vec![0, 50, 75, 88, 94, 97, 99]);
+    assert_eq!((-5i64).shrink().collect::<Vec<_>>(), vec![0, -3, -4]);
+    assert!(0i32.shrink().next().is_none());
+}
+
+#[test]
+fn large_input_shrinks_without_building_every_candidate() {
+    // fails whenever a 7 is present; 10^5 elements would be 10^10 work if built eagerly
+    let mut v = vec![1u32; 100_000];
+    v[76_543] = 7;
+    let first = v.shrink().nth(1).unwrap();
+    assert_eq!(first.len(), 50_000);
+
+    let f = Stress::new()
+        .seed(3)
+        .run(move |_| v.clone(), |v| v.contains(&7), |_| false)
+        .unwrap_err();
+    assert_eq!(f.input, vec![7]);
+}
+
+#[test]
+fn strings_shrink_by_chunks_then_chars() {
+    let s = "héllo".to_string();
+    let c: Vec<String> = s.shrink().collect();
+    assert_eq!(&c[..4], ["", "llo", "héo", "héll"]);
+    assert!(c.contains(&"aéllo".to_string()));
+}
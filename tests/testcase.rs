@@ -0,0 +1,45 @@
+use rust_files::testcase::{self, Mismatch, TestCase};
+
+#[test]
+fn extracts_input_and_expected() {
+    let src = "fn main() {}\n/*\ntestcase:\n2\n1 2\n\n3\n*/\n/* just a note */\n/*\ntestcase:\n1\n\nYES\n*/\n";
+    assert_eq!(
+        testcase::extract(src),
+        vec![
+            TestCase { input: "2\n1 2\n".into(), expected: "3\n".into() },
+            TestCase { input: "1\n".into(), expected: "YES\n".into() },
+        ]
+    );
+}
+
+#[test]
+fn block_without_blank_line_is_expected_output_only() {
+    let cases = testcase::extract("/*\ntestcase:\n42: 42\n0-1: 0\n*/");
+    assert_eq!(cases, vec![TestCase { input: String::new(), expected: "42: 42\n0-1: 0\n".into() }]);
+}
+
+#[test]
+fn repo_solutions_carry_samples() {
+    let cases = testcase::extract(include_str!("../src/bin/04b_cp_input2.rs"));
+    assert_eq!(cases.len(), 1);
+    assert!(cases[0].input.starts_with("5\n5\n1 2 3 2 1\n"));
+    assert_eq!(cases[0].expected, "8\n12\n0\n10\n18\n");
+    assert_eq!(testcase::extract(include_str!("../src/bin/04a_cp_input1.rs")).len(), 2);
+}
+
+#[test]
+fn diff_ignores_whitespace_layout() {
+    assert_eq!(testcase::diff_tokens("1 2\n3\n", "1\n2 3  "), None);
+}
+
+#[test]
+fn diff_reports_first_mismatch() {
+    assert_eq!(
+        testcase::diff_tokens("YES\nNO\n", "YES\nYES\n"),
+        Some(Mismatch { index: 1, expected: Some("NO".into()), actual: Some("YES".into()) })
+    );
+    assert_eq!(
+        testcase::diff_tokens("1 2", "1"),
+        Some(Mismatch { index: 1, expected: Some("2".into()), actual: None })
+    );
+}
@@ -0,0 +1,56 @@
+use std::process::Command;
+
+use rust_files::users::{mock, UserClient};
+
+#[tokio::test]
+async fn get_user_parses_every_field() {
+    let client = UserClient::new(&mock::spawn_local().await.unwrap());
+    let u = client.get_user(1).await.unwrap();
+    assert_eq!((u.id, u.name.as_str(), u.username.as_str()), (1, "Leanne Graham", "Bret"));
+    assert_eq!(u.email, "Sincere@april.biz");
+    assert_eq!(u.address.city, "Gwenborough");
+    assert_eq!(u.address.geo.lat, "-37.3159");
+    assert_eq!(u.company.name, "Romaguera-Crona");
+    assert_eq!(u.company.catch_phrase, "Multi-layered client-server neural-net"); // catchPhrase in the json
+}
+
+#[tokio::test]
+async fn list_users_matches_the_fixture() {
+    let client = UserClient::new(&mock::spawn_local().await.unwrap());
+    let users = client.list_users().await.unwrap();
+    assert_eq!(users.len(), 10);
+    assert_eq!(users.iter().map(|u| u.id).collect::<Vec<_>>(), (1..=10).collect::<Vec<_>>());
+    assert_eq!(users, mock::users());
+    assert_eq!(client.get_user(7).await.unwrap(), users[6]);
+}
+
+#[tokio::test]
+async fn unknown_user_is_a_404() {
+    let client = UserClient::new(&mock::spawn_local().await.unwrap());
+    let err = client.get_user(11).await.unwrap_err();
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(404));
+}
+
+#[test]
+fn routes() {
+    assert_eq!(mock::route("GET", "/users/2").0, 200);
+    assert_eq!(mock::route("GET", "/users/2/").0, 200);
+    assert_eq!(mock::route("GET", "/users?_limit=3").0, 200);
+    assert_eq!(mock::route("GET", "/users/abc"), (404, "{}".to_string()));
+    assert_eq!(mock::route("GET", "/posts").0, 404);
+    assert_eq!(mock::route("POST", "/users").0, 405);
+}
+
+#[test]
+fn demo_runs_offline() {
+    let out = Command::new(env!("CARGO_BIN_EXE_07f_tokio_reqwest_rand_result"))
+        .args(["--offline", "3"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.contains("user id: 3"), "{}", stdout);
+    assert!(stdout.contains("name: Clementine Bauch (@Samantha)"), "{}", stdout);
+    assert!(stdout.contains("company: Romaguera-Jacobson"), "{}", stdout);
+    assert!(out.stderr.is_empty(), "{}", String::from_utf8_lossy(&out.stderr));
+}
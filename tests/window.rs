@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_files::window::*;
+
+// O(n^2) reference: try every start
+fn brute_longest_unique(a: &[u8]) -> usize {
+    let mut best = 0;
+    for l in 0..a.len() {
+        let mut seen = [false; 256];
+        for &x in &a[l..] {
+            if seen[x as usize] {
+                break;
+            }
+            seen[x as usize] = true;
+        }
+        best = best.max(seen.iter().filter(|&&b| b).count());
+    }
+    best
+}
+
+#[test]
+fn longest_unique_substring_samples() {
+    assert_eq!(longest_unique_substring("abcabcbb"), 3);
+    assert_eq!(longest_unique_substring("bbbbb"), 1);
+    assert_eq!(longest_unique_substring("pwwkew"), 3);
+    assert_eq!(longest_unique_substring(""), 0);
+    assert_eq!(longest_unique_substring("abba"), 2);
+    assert_eq!(longest_unique_substring("héllo wörld"), 7);
+    assert_eq!(longest_unique(&[1, 2, 1, 3, 4, 3]), (1, 4));
+}
+
+#[test]
+fn longest_unique_matches_brute_force_and_window() {
+    let mut rng = StdRng::seed_from_u64(3);
+    for _ in 0..2000 {
+        let n = rng.gen_range(0..40);
+        let a: Vec<u8> = (0..n).map(|_| rng.gen_range(b'a'..b'g')).collect();
+        let (start, len) = longest_unique(&a);
+        assert_eq!(len, brute_longest_unique(&a), "{:?}", a);
+        assert_eq!(brute_longest_unique(&a[start..start + len]), len);
+
+        // same answer from the generic window with a duplicate counter as state
+        let add = |s: &mut (HashMap<u8, usize>, usize), x: &u8| {
+            let c = s.0.entry(*x).or_insert(0);
+            *c += 1;
+            if *c == 2 {
+                s.1 += 1;
+            }
+        };
+        let remove = |s: &mut (HashMap<u8, usize>, usize), x: &u8| {
+            let c = s.0.get_mut(x).unwrap();
+            *c -= 1;
+            if *c == 1 {
+                s.1 -= 1;
+            }
+        };
+        let w = longest_window(&a, (HashMap::new(), 0), add, remove, |s| s.1 == 0);
+        assert_eq!(w.1, len);
+    }
+}
+
+#[test]
+fn longest_window_with_sum_limit() {
+    let a = [4, 1, 1, 2, 1, 6, 1];
+    assert_eq!(longest_window(&a, 0, |s, x| *s += x, |s, x| *s -= x, |s| *s <= 5), (1, 4));
+    assert_eq!(longest_window(&a, 0, |s, x| *s += x, |s, x| *s -= x, |s| *s <= 0), (0, 0));
+    assert_eq!(longest_window(&[] as &[i32], 0, |s, x| *s += x, |s, x| *s -= x, |_| true), (0, 0));
+}
+
+#[test]
+fn fixed_windows_sums_and_distinct_counts() {
+    let a = [1, 3, -1, -3, 5, 3, 6, 7];
+    let sums = fixed_windows(&a, 3, 0, |s, x| *s += x, |s, x| *s -= x, |s| *s);
+    assert_eq!(sums, vec![3, -1, 1, 5, 14, 16]);
+    assert!(fixed_windows(&a, 9, 0, |s, x| *s += x, |s, x| *s -= x, |s| *s).is_empty());
+
+    let b = [1, 2, 1, 3, 3];
+    let distinct = fixed_windows(
+        &b,
+        2,
+        HashMap::new(),
+        |m: &mut HashMap<i32, i32>, x| *m.entry(*x).or_insert(0) += 1,
+        |m, x| {
+            *m.get_mut(x).unwrap() -= 1;
+            if m[x] == 0 {
+                m.remove(x);
+            }
+        },
+        |m| m.len(),
+    );
+    assert_eq!(distinct, vec![2, 2, 2, 1]);
+}
+
+#[test]
+fn last_seen_returns_previous_index() {
+    let mut seen = LastSeen::new();
+    assert_eq!(seen.see('a', 0), None);
+    assert_eq!(seen.see('a', 4), Some(0));
+    assert_eq!(seen.get(&'a'), Some(4));
+    assert_eq!(seen.get(&'b'), None);
+}